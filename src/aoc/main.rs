@@ -0,0 +1,199 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process;
+
+static DAY_TEMPLATE: &str = include_str!("day.rs.tmpl");
+static LEGACY_STUB: &str = include_str!("stub.rs.tmpl");
+
+fn parse_day(s: &str) -> Result<u8, String> {
+    match s.parse::<u8>() {
+        Ok(day) if (1..=25).contains(&day) => Ok(day),
+        _ => Err(format!("Invalid day: {}", s)),
+    }
+}
+
+fn render_template(day: u8) -> String {
+    DAY_TEMPLATE.replace("{{day}}", &format!("{:02}", day))
+}
+
+fn is_stub(source: &str, day: u8) -> bool {
+    let legacy = LEGACY_STUB.replace("{{day}}", &format!("{:02}", day));
+
+    source == render_template(day)
+        || source == legacy
+        || source == legacy.replace("fn solve(input:", "fn solve(_input:")
+}
+
+fn register_bin(manifest: &str, day: u8) -> Option<String> {
+    let name = format!("day{:02}", day);
+
+    if manifest.contains(&format!("name = \"{}\"", name)) {
+        return None;
+    }
+
+    let block = format!(
+        "[[bin]]\nname = \"{0}\"\npath = \"src/{0}/main.rs\"\n\n",
+        name
+    );
+
+    let insert_at = (1..day)
+        .rev()
+        .filter_map(|d| {
+            let path = format!("path = \"src/day{:02}/main.rs\"\n", d);
+            manifest.find(&path).map(|idx| idx + path.len())
+        })
+        .next()
+        .map(|idx| manifest[idx..].find("[[bin]]").map_or(idx, |i| idx + i))
+        .or_else(|| manifest.find("[[bin]]"))
+        .or_else(|| manifest.find("[dependencies]"))
+        .unwrap_or(manifest.len());
+
+    let mut manifest = manifest.to_string();
+    manifest.insert_str(insert_at, &block);
+
+    Some(manifest)
+}
+
+fn new_day(day: u8) -> Result<(), String> {
+    let dir = format!("src/day{:02}", day);
+    let path = format!("{}/main.rs", dir);
+
+    if Path::new(&path).exists() {
+        let source = fs::read_to_string(&path).map_err(|e| format!("{}: {}", path, e))?;
+        if !is_stub(&source, day) {
+            return Err(format!(
+                "{} is not an untouched stub, refusing to overwrite it",
+                path
+            ));
+        }
+    }
+
+    fs::create_dir_all(&dir).map_err(|e| format!("{}: {}", dir, e))?;
+    fs::write(&path, render_template(day)).map_err(|e| format!("{}: {}", path, e))?;
+    println!("Created {}", path);
+
+    let manifest = fs::read_to_string("Cargo.toml").map_err(|e| format!("Cargo.toml: {}", e))?;
+    if let Some(manifest) = register_bin(&manifest, day) {
+        fs::write("Cargo.toml", manifest).map_err(|e| format!("Cargo.toml: {}", e))?;
+        println!("Registered day{:02} in Cargo.toml", day);
+    }
+
+    Ok(())
+}
+
+fn run(args: &[String]) -> Result<(), String> {
+    match args {
+        [cmd, day] if cmd == "new" => new_day(parse_day(day)?),
+        _ => Err("Usage: aoc new <day>".to_string()),
+    }
+}
+
+fn main() {
+    let args = env::args().skip(1).collect::<Vec<String>>();
+
+    if let Err(e) = run(&args) {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{is_stub, parse_day, register_bin, render_template, LEGACY_STUB};
+
+    static MANIFEST: &str = "[package]
+name = \"aoc-2021\"
+
+[[bin]]
+name = \"day01\"
+path = \"src/day01/main.rs\"
+
+[[bin]]
+name = \"day03\"
+path = \"src/day03/main.rs\"
+
+[[bin]]
+name = \"aoc\"
+path = \"src/aoc/main.rs\"
+
+[dependencies]
+";
+
+    #[test]
+    fn test_parse_day() {
+        assert_eq!(parse_day("1"), Ok(1));
+        assert_eq!(parse_day("07"), Ok(7));
+        assert_eq!(parse_day("25"), Ok(25));
+        assert!(parse_day("0").is_err());
+        assert!(parse_day("26").is_err());
+        assert!(parse_day("x").is_err());
+    }
+
+    #[test]
+    fn test_render_template() {
+        let source = render_template(7);
+
+        assert!(source.contains("get_input(\"day07.txt\")"));
+        assert!(!source.contains("{{day}}"));
+        assert!(source.contains("#[cfg(test)]"));
+        assert!(is_stub(&source, 7));
+    }
+
+    #[test]
+    fn test_is_stub() {
+        let legacy = LEGACY_STUB.replace("{{day}}", "24");
+
+        assert!(is_stub(&render_template(24), 24));
+        assert!(is_stub(&legacy, 24));
+        assert!(is_stub(&legacy.replace("(input:", "(_input:"), 24));
+
+        assert!(!is_stub(&render_template(23), 24));
+        assert!(!is_stub(
+            &render_template(24).replace("(0, 0)", "(42, 0)"),
+            24
+        ));
+        assert!(!is_stub(
+            &format!(
+                "{}\nfn parse_input(input: &[String]) -> Vec<u32> {{\n    vec![]\n}}\n",
+                render_template(24)
+            ),
+            24
+        ));
+        assert!(!is_stub(
+            "fn solve(input: &[String]) -> (impl Display, impl Display) {
+    (input.len(), 0)
+}",
+            24
+        ));
+    }
+
+    #[test]
+    fn test_register_bin() {
+        let manifest = register_bin(MANIFEST, 2).unwrap();
+        assert!(manifest.contains(
+            "path = \"src/day01/main.rs\"
+
+[[bin]]
+name = \"day02\"
+path = \"src/day02/main.rs\"
+
+[[bin]]
+name = \"day03\""
+        ));
+
+        let manifest = register_bin(MANIFEST, 4).unwrap();
+        assert!(manifest.contains(
+            "path = \"src/day03/main.rs\"
+
+[[bin]]
+name = \"day04\"
+path = \"src/day04/main.rs\"
+
+[[bin]]
+name = \"aoc\""
+        ));
+
+        assert_eq!(register_bin(MANIFEST, 3), None);
+    }
+}
@@ -1,23 +1,117 @@
+use std::cmp::{self, Ordering};
+use std::collections::VecDeque;
 use std::fmt::Display;
+use std::ops::{Add, Sub};
 use std::time::Instant;
 
+use num::Zero;
+
 use aoc_2021::get_input_as_int;
 
-fn count_increases(values: &[u32], step: usize) -> u32 {
-    let mut increases = 0;
+#[derive(Debug, Default, PartialEq, Eq)]
+struct WindowStats {
+    increases: usize,
+    decreases: usize,
+    plateaus: usize,
+    longest_increasing_run: usize,
+    longest_decreasing_run: usize,
+}
+
+fn window_stats<T: Ord, I: IntoIterator<Item = T>>(values: I, step: usize) -> WindowStats {
+    assert!(step > 0, "Invalid step: {}", step);
+
+    let mut stats = WindowStats::default();
+    let mut window = VecDeque::with_capacity(step);
+
+    let mut increasing_run = 0;
+    let mut decreasing_run = 0;
+
+    for v in values {
+        if window.len() == step {
+            let previous = window.pop_front().unwrap();
+
+            match v.cmp(&previous) {
+                Ordering::Greater => {
+                    stats.increases += 1;
+                    increasing_run += 1;
+                    decreasing_run = 0;
+                }
+                Ordering::Less => {
+                    stats.decreases += 1;
+                    increasing_run = 0;
+                    decreasing_run += 1;
+                }
+                Ordering::Equal => {
+                    stats.plateaus += 1;
+                    increasing_run = 0;
+                    decreasing_run = 0;
+                }
+            }
+
+            stats.longest_increasing_run = cmp::max(stats.longest_increasing_run, increasing_run);
+            stats.longest_decreasing_run = cmp::max(stats.longest_decreasing_run, decreasing_run);
+        }
+
+        window.push_back(v);
+    }
+
+    stats
+}
+
+fn count_increases<T: Ord, I: IntoIterator<Item = T>>(values: I, step: usize) -> usize {
+    window_stats(values, step).increases
+}
+
+struct WindowSums<I: Iterator> {
+    values: I,
+    window: VecDeque<I::Item>,
+    size: usize,
+    sum: I::Item,
+}
+
+impl<T, I> Iterator for WindowSums<I>
+where
+    T: Copy + Zero + Add<Output = T> + Sub<Output = T>,
+    I: Iterator<Item = T>,
+{
+    type Item = T;
 
-    for i in 0..values.len() - step {
-        if values[i + step] > values[i] {
-            increases += 1;
+    fn next(&mut self) -> Option<T> {
+        loop {
+            let v = self.values.next()?;
+
+            self.sum = self.sum + v;
+            self.window.push_back(v);
+
+            if self.window.len() > self.size {
+                self.sum = self.sum - self.window.pop_front().unwrap();
+            }
+
+            if self.window.len() == self.size {
+                return Some(self.sum);
+            }
         }
     }
+}
 
-    increases
+fn window_sums<T, I>(values: I, size: usize) -> WindowSums<I::IntoIter>
+where
+    T: Copy + Zero + Add<Output = T> + Sub<Output = T>,
+    I: IntoIterator<Item = T>,
+{
+    assert!(size > 0, "Invalid window size: {}", size);
+
+    WindowSums {
+        values: values.into_iter(),
+        window: VecDeque::with_capacity(size + 1),
+        size,
+        sum: T::zero(),
+    }
 }
 
 fn solve(input: &[u32]) -> (impl Display, impl Display) {
     let p1 = count_increases(input, 1);
-    let p2 = count_increases(input, 3);
+    let p2 = count_increases(window_sums(input.iter().copied(), 3), 1);
 
     (p1, p2)
 }
@@ -38,11 +132,11 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
-    use crate::count_increases;
+    use aoc_2021::{example_test, ints};
 
-    #[test]
-    fn test_p1() {
-        let input = "199
+    use crate::{count_increases, solve, window_stats, window_sums, WindowStats};
+
+    static TEST_INPUT: &str = "199
 200
 208
 210
@@ -53,35 +147,83 @@ mod tests {
 260
 263";
 
-        let input = input
-            .split("\n")
-            .map(|v| v.parse().unwrap())
-            .collect::<Vec<u32>>();
-        let res = count_increases(&input, 1);
+    #[test]
+    fn test_p1() {
+        let res = count_increases(ints::<u32>(TEST_INPUT), 1);
 
         assert_eq!(7, res);
     }
 
     #[test]
     fn test_p2() {
-        let input = "199
-200
-208
-210
-200
-207
-240
-269
-260
-263";
+        let res = count_increases(ints::<u32>(TEST_INPUT), 3);
 
-        let input = input
-            .split("\n")
-            .map(|v| v.parse().unwrap())
-            .collect::<Vec<u32>>();
+        assert_eq!(5, res);
+    }
 
-        let res = count_increases(&input, 3);
+    #[test]
+    fn test_window_stats() {
+        let stats = window_stats(ints::<u32>(TEST_INPUT), 1);
+
+        assert_eq!(
+            stats,
+            WindowStats {
+                increases: 7,
+                decreases: 2,
+                plateaus: 0,
+                longest_increasing_run: 3,
+                longest_decreasing_run: 1,
+            }
+        );
+
+        let stats = window_stats(window_sums(ints::<u32>(TEST_INPUT), 3), 1);
+
+        assert_eq!(
+            stats,
+            WindowStats {
+                increases: 5,
+                decreases: 1,
+                plateaus: 1,
+                longest_increasing_run: 4,
+                longest_decreasing_run: 1,
+            }
+        );
+    }
 
-        assert_eq!(5, res);
+    #[test]
+    fn test_window_stats_with_short_input() {
+        assert_eq!(window_stats([3, 1], 3), WindowStats::default());
+        assert_eq!(window_stats(Vec::<i64>::new(), 1), WindowStats::default());
+    }
+
+    #[test]
+    fn test_window_stats_with_signed_values() {
+        let stats = window_stats([-3, -5, -5, -7, -9, 0], 1);
+
+        assert_eq!(stats.increases, 1);
+        assert_eq!(stats.decreases, 3);
+        assert_eq!(stats.plateaus, 1);
+        assert_eq!(stats.longest_decreasing_run, 2);
+    }
+
+    #[test]
+    fn test_window_sums() {
+        let sums = window_sums(ints::<u32>(TEST_INPUT), 3).collect::<Vec<u32>>();
+
+        assert_eq!(sums, vec![607, 618, 618, 617, 647, 716, 769, 792]);
+        assert_eq!(window_sums([1, 2], 3).count(), 0);
     }
+
+    #[test]
+    fn test_window_stats_on_stream() {
+        let values = (0..1_000_000u64).map(|i| i % 1000);
+
+        let stats = window_stats(window_sums(values, 10), 1);
+
+        assert_eq!(stats.increases + stats.decreases + stats.plateaus, 999_990);
+        assert_eq!(stats.decreases, 9990);
+        assert_eq!(stats.longest_increasing_run, 990);
+    }
+
+    example_test!(test_solve, ints: TEST_INPUT => 7, 5);
 }
@@ -1,50 +1,310 @@
-use std::fmt::Display;
+use std::collections::HashMap;
+use std::env;
+use std::fmt::{Display, Write};
+use std::fs;
+use std::path::Path;
+use std::slice;
+use std::str::FromStr;
 use std::time::Instant;
 
 use aoc_2021::get_input;
 
-fn parse_commands(input: &[String]) -> Vec<(&str, u32)> {
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Command {
+    Forward(i64),
+    Down(i64),
+    Up(i64),
+    Extra(String, Option<i64>),
+}
+
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Command, Self::Err> {
+        let (cmd, arg) = match s.split_once(' ') {
+            Some((cmd, n)) => match n.parse::<i64>() {
+                Ok(n) => (cmd, Some(n)),
+                Err(_) => return Err(format!("Invalid argument: {}", s)),
+            },
+            None => (s, None),
+        };
+
+        match (cmd, arg) {
+            ("forward", Some(n)) => Ok(Command::Forward(n)),
+            ("down", Some(n)) => Ok(Command::Down(n)),
+            ("up", Some(n)) => Ok(Command::Up(n)),
+            ("forward" | "down" | "up", None) => Err(format!("Missing argument: {}", s)),
+            ("", _) => Err(format!("Invalid command: {}", s)),
+            (cmd, arg) => Ok(Command::Extra(cmd.to_string(), arg)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Navigation {
+    Plain,
+    Aim,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct State {
+    position: i64,
+    depth: i64,
+    aim: i64,
+}
+
+type Instruction = fn(&mut Submarine, Option<i64>) -> Result<(), String>;
+
+#[derive(Debug, Clone)]
+struct Submarine {
+    navigation: Navigation,
+    position: i64,
+    depth: i64,
+    aim: i64,
+    instructions: HashMap<String, Instruction>,
+}
+
+impl Submarine {
+    fn new(navigation: Navigation) -> Self {
+        Self {
+            navigation,
+            position: 0,
+            depth: 0,
+            aim: 0,
+            instructions: HashMap::new(),
+        }
+    }
+
+    fn register(&mut self, name: &str, instruction: Instruction) {
+        self.instructions.insert(name.to_string(), instruction);
+    }
+
+    fn execute(&mut self, command: &Command) -> Result<(), String> {
+        match (self.navigation, command) {
+            (Navigation::Plain, Command::Forward(n)) => self.position += n,
+            (Navigation::Plain, Command::Down(n)) => self.depth += n,
+            (Navigation::Plain, Command::Up(n)) => self.depth -= n,
+            (Navigation::Aim, Command::Forward(n)) => {
+                self.position += n;
+                self.depth += self.aim * n;
+            }
+            (Navigation::Aim, Command::Down(n)) => self.aim += n,
+            (Navigation::Aim, Command::Up(n)) => self.aim -= n,
+            (_, Command::Extra(name, arg)) => match self.instructions.get(name) {
+                Some(instruction) => instruction(self, *arg)?,
+                None => return Err(format!("Unknown command: {}", name)),
+            },
+        }
+
+        Ok(())
+    }
+
+    fn state(&self) -> State {
+        State {
+            position: self.position,
+            depth: self.depth,
+            aim: self.aim,
+        }
+    }
+
+    fn trace(self, commands: &[Command]) -> Trace<'_> {
+        Trace {
+            submarine: self,
+            commands: commands.iter(),
+            started: false,
+            failed: false,
+        }
+    }
+
+    fn run(&mut self, commands: &[Command]) -> Result<(), String> {
+        for cmd in commands {
+            self.execute(cmd)?;
+        }
+
+        Ok(())
+    }
+}
+
+struct Trace<'a> {
+    submarine: Submarine,
+    commands: slice::Iter<'a, Command>,
+    started: bool,
+    failed: bool,
+}
+
+impl Iterator for Trace<'_> {
+    type Item = Result<State, String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            return Some(Ok(self.submarine.state()));
+        }
+
+        if self.failed {
+            return None;
+        }
+
+        let cmd = self.commands.next()?;
+
+        match self.submarine.execute(cmd) {
+            Ok(()) => Some(Ok(self.submarine.state())),
+            Err(e) => {
+                self.failed = true;
+                Some(Err(e))
+            }
+        }
+    }
+}
+
+fn parse_commands(input: &[String]) -> Vec<Command> {
     input
         .iter()
-        .map(|s| s.split_once(' ').unwrap())
-        .map(|(cmd, n)| (cmd, n.parse().unwrap()))
+        .map(|s| match s.parse() {
+            Ok(cmd) => cmd,
+            Err(e) => panic!("{}", e),
+        })
         .collect()
 }
 
-fn part1(commands: &[(&str, u32)]) -> u32 {
-    let mut pos = 0;
-    let mut depth = 0;
-
-    for (cmd, n) in commands {
-        match *cmd {
-            "forward" => pos += n,
-            "down" => depth += n,
-            "up" => depth -= n,
-            _ => panic!("Invalid command: {}", cmd),
+fn register_extra_instructions(submarine: &mut Submarine) {
+    submarine.register("back", |sub, n| match n {
+        Some(n) => {
+            sub.position -= n;
+            Ok(())
         }
+        None => Err("Missing argument: back".to_string()),
+    });
+
+    submarine.register("reset", |sub, _| {
+        sub.position = 0;
+        sub.depth = 0;
+        sub.aim = 0;
+        Ok(())
+    });
+}
+
+fn navigate(navigation: Navigation, commands: &[Command]) -> i64 {
+    let mut submarine = Submarine::new(navigation);
+    register_extra_instructions(&mut submarine);
+
+    if let Err(e) = submarine.run(commands) {
+        panic!("{}", e);
     }
 
-    pos * depth
+    submarine.position * submarine.depth
 }
 
-fn part2(commands: &[(&str, u32)]) -> u32 {
-    let mut pos = 0;
-    let mut depth = 0;
-    let mut aim = 0;
+fn trace_course(navigation: Navigation, commands: &[Command]) -> Vec<State> {
+    let mut submarine = Submarine::new(navigation);
+    register_extra_instructions(&mut submarine);
 
-    for (cmd, n) in commands {
-        match *cmd {
-            "forward" => {
-                pos += n;
-                depth += aim * n;
-            }
-            "down" => aim += n,
-            "up" => aim -= n,
-            _ => panic!("Invalid command: {}", cmd),
+    match submarine.trace(commands).collect() {
+        Ok(states) => states,
+        Err(e) => panic!("{}", e),
+    }
+}
+
+fn to_csv(states: &[State]) -> String {
+    let mut csv = String::from("step,position,depth,aim\n");
+
+    for (step, s) in states.iter().enumerate() {
+        writeln!(csv, "{},{},{},{}", step, s.position, s.depth, s.aim).unwrap();
+    }
+
+    csv
+}
+
+static SVG_WIDTH: f64 = 800.0;
+static SVG_HEIGHT: f64 = 400.0;
+static SVG_MARGIN: f64 = 20.0;
+static SVG_COLORS: [&str; 4] = ["#1f77b4", "#d62728", "#2ca02c", "#ff7f0e"];
+
+fn to_depth_profile_svg(profiles: &[(&str, &[State])]) -> String {
+    let states = || profiles.iter().flat_map(|(_, states)| states.iter());
+
+    let min_pos = states().map(|s| s.position).min().unwrap_or(0);
+    let max_pos = states().map(|s| s.position).max().unwrap_or(0);
+    let min_depth = states().map(|s| s.depth).min().unwrap_or(0);
+    let max_depth = states().map(|s| s.depth).max().unwrap_or(0);
+
+    let scale_x = (SVG_WIDTH - 2.0 * SVG_MARGIN) / (max_pos - min_pos).max(1) as f64;
+    let scale_y = (SVG_HEIGHT - 2.0 * SVG_MARGIN) / (max_depth - min_depth).max(1) as f64;
+
+    let mut svg = String::new();
+
+    writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{0}" height="{1}" viewBox="0 0 {0} {1}">"#,
+        SVG_WIDTH, SVG_HEIGHT
+    )
+    .unwrap();
+    writeln!(svg, r#"<rect width="100%" height="100%" fill="white"/>"#).unwrap();
+
+    for (idx, (name, states)) in profiles.iter().enumerate() {
+        let color = SVG_COLORS[idx % SVG_COLORS.len()];
+
+        let points = states
+            .iter()
+            .map(|s| {
+                let x = SVG_MARGIN + (s.position - min_pos) as f64 * scale_x;
+                let y = SVG_MARGIN + (s.depth - min_depth) as f64 * scale_y;
+                format!("{:.1},{:.1}", x, y)
+            })
+            .collect::<Vec<String>>()
+            .join(" ");
+
+        writeln!(
+            svg,
+            r#"<polyline fill="none" stroke="{}" stroke-width="1" points="{}"/>"#,
+            color, points
+        )
+        .unwrap();
+        writeln!(
+            svg,
+            r#"<text x="{}" y="{}" fill="{}" font-family="monospace" font-size="12">{}</text>"#,
+            SVG_MARGIN,
+            SVG_HEIGHT - SVG_MARGIN / 2.0 - 14.0 * (profiles.len() - 1 - idx) as f64,
+            color,
+            name
+        )
+        .unwrap();
+    }
+
+    svg.push_str("</svg>\n");
+
+    svg
+}
+
+fn export_course(commands: &[Command], dir: &Path) {
+    let plain = trace_course(Navigation::Plain, commands);
+    let aim = trace_course(Navigation::Aim, commands);
+
+    let files = [
+        ("day02_plain.csv", to_csv(&plain)),
+        ("day02_aim.csv", to_csv(&aim)),
+        (
+            "day02_depth.svg",
+            to_depth_profile_svg(&[("plain", &plain), ("aim", &aim)]),
+        ),
+    ];
+
+    for (name, content) in files {
+        let path = dir.join(name);
+
+        match fs::write(&path, content) {
+            Ok(()) => println!("Wrote {}", path.display()),
+            Err(e) => panic!("Unable to write {}: {}", path.display(), e),
         }
     }
+}
+
+fn part1(commands: &[Command]) -> i64 {
+    navigate(Navigation::Plain, commands)
+}
 
-    pos * depth
+fn part2(commands: &[Command]) -> i64 {
+    navigate(Navigation::Aim, commands)
 }
 
 fn solve(input: &[String]) -> (impl Display, impl Display) {
@@ -67,51 +327,185 @@ fn main() {
     println!("Part 1: {}", r1);
     println!("Part 2: {}", r2);
     println!("Duration: {:.3}ms", t);
+
+    if let Some(dir) = env::args().nth(1) {
+        export_course(&parse_commands(&input), Path::new(&dir));
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{parse_commands, part1, part2};
+    use aoc_2021::{example_test, lines};
 
-    #[test]
-    fn test_p1() {
-        let input = "forward 5
+    use crate::{
+        parse_commands, part1, part2, register_extra_instructions, solve, to_csv,
+        to_depth_profile_svg, trace_course, Command, Navigation, State, Submarine,
+    };
+
+    static TEST_INPUT: &str = "forward 5
 down 5
 forward 8
 up 3
 down 8
 forward 2";
 
-        let input = input
-            .split("\n")
-            .map(|v| v.to_string())
-            .collect::<Vec<String>>();
+    #[test]
+    fn test_p1() {
+        let input = lines(TEST_INPUT);
+        let commands = parse_commands(&input);
 
+        assert_eq!(150, part1(&commands));
+    }
+
+    #[test]
+    fn test_p2() {
+        let input = lines(TEST_INPUT);
         let commands = parse_commands(&input);
 
-        let res = part1(&commands);
+        assert_eq!(900, part2(&commands));
+    }
 
-        assert_eq!(150, res);
+    #[test]
+    fn test_parse_command() {
+        assert_eq!("forward 5".parse(), Ok(Command::Forward(5)));
+        assert_eq!("down 5".parse(), Ok(Command::Down(5)));
+        assert_eq!("up 3".parse(), Ok(Command::Up(3)));
+        assert_eq!(
+            "back 2".parse(),
+            Ok(Command::Extra("back".to_string(), Some(2)))
+        );
+        assert_eq!(
+            "reset".parse(),
+            Ok(Command::Extra("reset".to_string(), None))
+        );
+
+        assert!("up".parse::<Command>().is_err());
+        assert!("down x".parse::<Command>().is_err());
+        assert!("".parse::<Command>().is_err());
     }
 
     #[test]
-    fn test_p2() {
-        let input = "forward 5
-down 5
-forward 8
-up 3
-down 8
-forward 2";
+    fn test_negative_depth() {
+        let commands = parse_commands(&lines("forward 2\nup 3"));
 
-        let input = input
-            .split("\n")
-            .map(|v| v.to_string())
-            .collect::<Vec<String>>();
+        assert_eq!(part1(&commands), -6);
+    }
 
-        let commands = parse_commands(&input);
+    #[test]
+    fn test_unknown_command() {
+        let mut submarine = Submarine::new(Navigation::Plain);
+        let commands = parse_commands(&lines("forward 2\nsideways 1"));
+
+        assert_eq!(
+            submarine.run(&commands),
+            Err("Unknown command: sideways".to_string())
+        );
+        assert_eq!(submarine.position, 2);
+    }
+
+    #[test]
+    fn test_extra_commands() {
+        let commands = parse_commands(&lines(
+            "forward 5\ndown 5\nback 2\nforward 8\nreset\nforward 2\ndown 1",
+        ));
+
+        for navigation in [Navigation::Plain, Navigation::Aim] {
+            let mut submarine = Submarine::new(navigation);
+
+            register_extra_instructions(&mut submarine);
+
+            assert_eq!(submarine.run(&commands), Ok(()));
+            assert_eq!(submarine.position, 2);
+
+            match navigation {
+                Navigation::Plain => assert_eq!(submarine.depth, 1),
+                Navigation::Aim => assert_eq!((submarine.depth, submarine.aim), (0, 1)),
+            }
+        }
+    }
+
+    #[test]
+    fn test_trace() {
+        let commands = parse_commands(&lines(TEST_INPUT));
+
+        let states = Submarine::new(Navigation::Aim)
+            .trace(&commands)
+            .collect::<Result<Vec<State>, String>>()
+            .unwrap();
 
-        let res = part2(&commands);
+        assert_eq!(states.len(), commands.len() + 1);
+        assert_eq!(
+            states[0],
+            State {
+                position: 0,
+                depth: 0,
+                aim: 0
+            }
+        );
+        assert_eq!(
+            states[3],
+            State {
+                position: 13,
+                depth: 40,
+                aim: 5
+            }
+        );
+        assert_eq!(
+            *states.last().unwrap(),
+            State {
+                position: 15,
+                depth: 60,
+                aim: 10
+            }
+        );
+    }
 
-        assert_eq!(900, res);
+    #[test]
+    fn test_trace_stops_on_error() {
+        let commands = parse_commands(&lines("forward 2\nsideways 1\nforward 3"));
+
+        let states = Submarine::new(Navigation::Plain)
+            .trace(&commands)
+            .collect::<Vec<Result<State, String>>>();
+
+        assert_eq!(states.len(), 3);
+        assert_eq!(states[2], Err("Unknown command: sideways".to_string()));
     }
+
+    #[test]
+    fn test_to_csv() {
+        let commands = parse_commands(&lines(TEST_INPUT));
+
+        assert_eq!(
+            to_csv(&trace_course(Navigation::Plain, &commands)),
+            "step,position,depth,aim
+0,0,0,0
+1,5,0,0
+2,5,5,0
+3,13,5,0
+4,13,2,0
+5,13,10,0
+6,15,10,0
+"
+        );
+    }
+
+    #[test]
+    fn test_to_depth_profile_svg() {
+        let commands = parse_commands(&lines(TEST_INPUT));
+        let plain = trace_course(Navigation::Plain, &commands);
+        let aim = trace_course(Navigation::Aim, &commands);
+
+        let svg = to_depth_profile_svg(&[("plain", &plain), ("aim", &aim)]);
+
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.ends_with("</svg>\n"));
+        assert_eq!(svg.matches("<polyline ").count(), 2);
+        assert!(svg.contains(">plain</text>"));
+        assert!(svg.contains(">aim</text>"));
+        assert!(svg.contains(r#"points="20.0,20.0 "#));
+        assert!(svg.contains(r#" 780.0,380.0""#));
+    }
+
+    example_test!(test_solve, TEST_INPUT => 150, 900);
 }
@@ -1,102 +1,282 @@
-use std::fmt::Display;
+use std::cmp::Ordering;
+use std::env;
+use std::fmt::{self, Display};
+use std::str::FromStr;
 use std::time::Instant;
 
+use num::BigUint;
+
 use aoc_2021::get_input;
 
-fn parse_values(input: &[String]) -> Vec<u32> {
-    input
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Bits(Vec<bool>);
+
+impl Bits {
+    fn width(&self) -> usize {
+        self.0.len()
+    }
+
+    fn bit(&self, idx: usize) -> bool {
+        self.0[idx]
+    }
+
+    fn invert(&self) -> Bits {
+        Bits(self.0.iter().map(|b| !b).collect())
+    }
+
+    fn value(&self) -> BigUint {
+        self.0.iter().fold(BigUint::from(0u32), |acc, &b| {
+            (acc << 1u32) + BigUint::from(b as u32)
+        })
+    }
+}
+
+impl FromStr for Bits {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Bits, Self::Err> {
+        s.chars()
+            .map(|c| match c {
+                '0' => Ok(false),
+                '1' => Ok(true),
+                _ => Err(format!("Invalid bit: {}", c)),
+            })
+            .collect::<Result<Vec<bool>, String>>()
+            .map(Bits)
+    }
+}
+
+impl Display for Bits {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for &b in &self.0 {
+            write!(f, "{}", if b { '1' } else { '0' })?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Diagnostic {
+    gamma: Bits,
+    epsilon: Bits,
+    o2: Bits,
+    co2: Bits,
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let ratings = [
+            ("Gamma", &self.gamma),
+            ("Epsilon", &self.epsilon),
+            ("O2", &self.o2),
+            ("CO2", &self.co2),
+        ];
+
+        for (name, bits) in ratings {
+            writeln!(f, "{}: {} ({})", name, bits, bits.value())?;
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_values(input: &[String]) -> Vec<Bits> {
+    let values = input
         .iter()
-        .map(|s| {
-            let mut n: u32 = 0;
-            for c in s.chars() {
-                n <<= 1;
-                if c == '1' {
-                    n |= 1;
-                }
-            }
-
-            n
+        .filter(|s| !s.is_empty())
+        .map(|s| match s.parse::<Bits>() {
+            Ok(bits) => bits,
+            Err(e) => panic!("{}", e),
         })
-        .collect()
+        .collect::<Vec<Bits>>();
+
+    if let Some(first) = values.first() {
+        if let Some(v) = values.iter().find(|v| v.width() != first.width()) {
+            panic!("Inconsistent width: {} and {}", first, v);
+        }
+    }
+
+    values
 }
 
-fn part1(values: &[u32]) -> u32 {
+fn get_width(values: &[Bits]) -> usize {
+    values.first().map_or(0, |v| v.width())
+}
+
+fn gamma_rate(values: &[Bits]) -> Bits {
     let nb_entries = values.len();
-    let nb_bits = (*values.iter().max().unwrap() as f32).log2().ceil() as usize;
 
-    let mut gamma = 0;
+    let bits = (0..get_width(values))
+        .map(|idx| {
+            let nb_ones = values.iter().filter(|v| v.bit(idx)).count();
+
+            nb_ones * 2 >= nb_entries
+        })
+        .collect();
 
-    let mut i = 1 << (nb_bits - 1);
+    Bits(bits)
+}
 
-    while i > 0 {
-        let nb_ones = values.iter().filter(|&&n| n & i != 0).count();
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Commonality {
+    Most,
+    Least,
+}
 
-        if nb_ones >= nb_entries / 2 {
-            gamma |= i
-        }
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BitOrder {
+    MsbFirst,
+    LsbFirst,
+}
 
-        i >>= 1;
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct BitCriteria {
+    commonality: Commonality,
+    tie_break: bool,
+    order: BitOrder,
+}
+
+static O2_CRITERIA: BitCriteria = BitCriteria {
+    commonality: Commonality::Most,
+    tie_break: true,
+    order: BitOrder::MsbFirst,
+};
+
+static CO2_CRITERIA: BitCriteria = BitCriteria {
+    commonality: Commonality::Least,
+    tie_break: false,
+    order: BitOrder::MsbFirst,
+};
+
+impl FromStr for BitCriteria {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<BitCriteria, Self::Err> {
+        let parts = s.split(':').collect::<Vec<&str>>();
+
+        let (commonality, tie_break, order) = match parts[..] {
+            [c, t, o] => (c, t, o),
+            _ => return Err(format!("Invalid criteria: {}", s)),
+        };
+
+        let commonality = match commonality {
+            "most" => Commonality::Most,
+            "least" => Commonality::Least,
+            _ => return Err(format!("Invalid commonality: {}", commonality)),
+        };
+
+        let tie_break = match tie_break {
+            "0" => false,
+            "1" => true,
+            _ => return Err(format!("Invalid tie break bit: {}", tie_break)),
+        };
+
+        let order = match order {
+            "msb" => BitOrder::MsbFirst,
+            "lsb" => BitOrder::LsbFirst,
+            _ => return Err(format!("Invalid bit order: {}", order)),
+        };
+
+        Ok(BitCriteria {
+            commonality,
+            tie_break,
+            order,
+        })
     }
+}
 
-    gamma * (gamma ^ ((1 << nb_bits) - 1))
+#[derive(Debug, PartialEq, Eq)]
+struct Elimination {
+    position: usize,
+    kept_bit: bool,
+    eliminated: Vec<Bits>,
 }
 
-fn part2(values: &[u32]) -> u32 {
-    let nb_bits = (*values.iter().max().unwrap() as f32).log2().ceil() as usize;
+#[derive(Debug, PartialEq, Eq)]
+struct FilterResult {
+    value: Bits,
+    history: Vec<Elimination>,
+}
 
-    let mut o2_candidates = values.to_vec();
-    let mut co2_candidates = values.to_vec();
+impl BitCriteria {
+    fn select_bit(&self, nb_ones: usize, nb_values: usize) -> bool {
+        let nb_zeroes = nb_values - nb_ones;
 
-    let mut i = 1 << (nb_bits - 1);
-    while o2_candidates.len() > 1 {
-        let nb_ones = o2_candidates.iter().filter(|&&n| n & i != 0).count();
+        if nb_ones == 0 || nb_zeroes == 0 {
+            return nb_ones > 0;
+        }
 
-        if nb_ones >= (o2_candidates.len() - nb_ones) {
-            o2_candidates = o2_candidates
-                .iter()
-                .filter(|&&n| n & i != 0)
-                .copied()
-                .collect();
-        } else {
-            o2_candidates = o2_candidates
-                .iter()
-                .filter(|&&n| n & i == 0)
-                .copied()
-                .collect();
+        match (nb_ones.cmp(&nb_zeroes), self.commonality) {
+            (Ordering::Equal, _) => self.tie_break,
+            (Ordering::Greater, Commonality::Most) | (Ordering::Less, Commonality::Least) => true,
+            (Ordering::Less, Commonality::Most) | (Ordering::Greater, Commonality::Least) => false,
         }
+    }
 
-        i >>= 1;
+    fn positions(&self, width: usize) -> Box<dyn Iterator<Item = usize>> {
+        match self.order {
+            BitOrder::MsbFirst => Box::new(0..width),
+            BitOrder::LsbFirst => Box::new((0..width).rev()),
+        }
     }
+}
 
-    let mut i = 1 << (nb_bits - 1);
-    while co2_candidates.len() > 1 {
-        let nb_zeroes = co2_candidates.iter().filter(|&&n| n & i == 0).count();
+fn filter_by_criteria(values: &[Bits], criteria: &BitCriteria) -> FilterResult {
+    let mut candidates = values.to_vec();
+    let mut history = Vec::new();
 
-        if nb_zeroes <= (co2_candidates.len() - nb_zeroes) {
-            co2_candidates = co2_candidates
-                .iter()
-                .filter(|&&n| n & i == 0)
-                .copied()
-                .collect();
-        } else {
-            co2_candidates = co2_candidates
-                .iter()
-                .filter(|&&n| n & i != 0)
-                .copied()
-                .collect();
+    for position in criteria.positions(get_width(values)) {
+        if candidates.len() <= 1 {
+            break;
         }
 
-        i >>= 1;
+        let nb_ones = candidates.iter().filter(|v| v.bit(position)).count();
+        let kept_bit = criteria.select_bit(nb_ones, candidates.len());
+
+        let (kept, eliminated) = candidates
+            .into_iter()
+            .partition(|v| v.bit(position) == kept_bit);
+        candidates = kept;
+
+        history.push(Elimination {
+            position,
+            kept_bit,
+            eliminated,
+        });
     }
 
-    o2_candidates.first().unwrap() * co2_candidates.first().unwrap()
+    match candidates.into_iter().next() {
+        Some(value) => FilterResult { value, history },
+        None => panic!("No values to filter"),
+    }
+}
+
+fn diagnose(values: &[Bits]) -> Diagnostic {
+    let gamma = gamma_rate(values);
+    let epsilon = gamma.invert();
+
+    Diagnostic {
+        gamma,
+        epsilon,
+        o2: filter_by_criteria(values, &O2_CRITERIA).value,
+        co2: filter_by_criteria(values, &CO2_CRITERIA).value,
+    }
+}
+
+fn part1(diagnostic: &Diagnostic) -> BigUint {
+    diagnostic.gamma.value() * diagnostic.epsilon.value()
+}
+
+fn part2(diagnostic: &Diagnostic) -> BigUint {
+    diagnostic.o2.value() * diagnostic.co2.value()
 }
 
-fn solve(input: &[String]) -> (impl Display, impl Display) {
-    let values = parse_values(input);
+fn solve(values: &[Bits]) -> (impl Display, impl Display) {
+    let diagnostic = diagnose(values);
 
-    let p1 = part1(&values);
-    let p2 = part2(&values);
+    let p1 = part1(&diagnostic);
+    let p2 = part2(&diagnostic);
     (p1, p2)
 }
 
@@ -105,22 +285,55 @@ fn main() {
 
     let start = Instant::now();
 
-    let (r1, r2) = solve(input.as_slice());
+    let values = parse_values(&input);
+    let (r1, r2) = solve(&values);
 
     let t = start.elapsed().as_micros() as f64 / 1000.0;
 
     println!("Part 1: {}", r1);
     println!("Part 2: {}", r2);
     println!("Duration: {:.3}ms", t);
+
+    for arg in env::args().skip(1) {
+        if arg == "diagnostic" {
+            print!("{}", diagnose(&values));
+            continue;
+        }
+
+        let criteria = match arg.parse::<BitCriteria>() {
+            Ok(criteria) => criteria,
+            Err(e) => panic!("{}", e),
+        };
+
+        let res = filter_by_criteria(&values, &criteria);
+
+        println!("{}: {} ({})", arg, res.value, res.value.value());
+
+        for e in res.history {
+            println!(
+                "  bit {}: kept {}, eliminated {}",
+                e.position,
+                e.kept_bit as u8,
+                e.eliminated.len()
+            );
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{parse_values, part1, part2};
+    use aoc_2021::{example_test, lines};
 
-    #[test]
-    fn test_p1() {
-        let input = "00100
+    use std::str::FromStr;
+
+    use num::BigUint;
+
+    use crate::{
+        diagnose, filter_by_criteria, parse_values, part1, part2, solve, BitCriteria, BitOrder,
+        Bits, Commonality, CO2_CRITERIA, O2_CRITERIA,
+    };
+
+    static TEST_INPUT: &str = "00100
 11110
 10110
 10111
@@ -133,38 +346,166 @@ mod tests {
 00010
 01010";
 
-        let input = input
-            .split("\n")
-            .map(|v| v.to_string())
-            .collect::<Vec<String>>();
-
-        let res = part1(&parse_values(&input));
+    #[test]
+    fn test_p1() {
+        let res = part1(&diagnose(&parse_values(&lines(TEST_INPUT))));
 
-        assert_eq!(198, res);
+        assert_eq!(BigUint::from(198u32), res);
     }
 
     #[test]
     fn test_p2() {
-        let input = "00100
-11110
-10110
-10111
-10101
-01111
-00111
-11100
-10000
-11001
-00010
-01010";
+        let res = part2(&diagnose(&parse_values(&lines(TEST_INPUT))));
+
+        assert_eq!(BigUint::from(230u32), res);
+    }
+
+    #[test]
+    fn test_diagnose() {
+        let diagnostic = diagnose(&parse_values(&lines(TEST_INPUT)));
+
+        assert_eq!(diagnostic.gamma.to_string(), "10110");
+        assert_eq!(diagnostic.epsilon.to_string(), "01001");
+        assert_eq!(diagnostic.o2.to_string(), "10111");
+        assert_eq!(diagnostic.co2.to_string(), "01010");
+
+        assert_eq!(
+            diagnostic.to_string(),
+            "Gamma: 10110 (22)
+Epsilon: 01001 (9)
+O2: 10111 (23)
+CO2: 01010 (10)
+"
+        );
+    }
+
+    #[test]
+    fn test_width_from_line_length() {
+        let values = parse_values(&lines("0010\n0111\n0100"));
+        let diagnostic = diagnose(&values);
+
+        assert_eq!(diagnostic.gamma.to_string(), "0110");
+        assert_eq!(diagnostic.epsilon.to_string(), "1001");
+        assert_eq!(part1(&diagnostic), BigUint::from(6u32 * 9));
+    }
+
+    #[test]
+    fn test_wide_values() {
+        let a = format!("1{}", "0".repeat(99));
+        let b = format!("1{}1", "0".repeat(98));
+        let c = format!("0{}1", "1".repeat(98));
+
+        let values = parse_values(&[a.clone(), b.clone(), c]);
+        let diagnostic = diagnose(&values);
+
+        assert_eq!(diagnostic.gamma.width(), 100);
+        assert_eq!(diagnostic.gamma.to_string(), b);
+        assert_eq!(diagnostic.o2.to_string(), b);
+        assert_eq!(
+            diagnostic.co2.value(),
+            (BigUint::from(1u32) << 99u32) - 1u32
+        );
+        assert_eq!(
+            Bits::from_str(&a).unwrap().value(),
+            BigUint::from(1u32) << 99u32
+        );
+    }
+
+    #[test]
+    fn test_filter_history() {
+        let values = parse_values(&lines(TEST_INPUT));
 
-        let input = input
-            .split("\n")
-            .map(|v| v.to_string())
-            .collect::<Vec<String>>();
+        let res = filter_by_criteria(&values, &O2_CRITERIA);
 
-        let res = part2(&parse_values(&input));
+        assert_eq!(res.value.to_string(), "10111");
+        assert_eq!(
+            res.history
+                .iter()
+                .map(|e| (e.position, e.kept_bit, e.eliminated.len()))
+                .collect::<Vec<(usize, bool, usize)>>(),
+            vec![
+                (0, true, 5),
+                (1, false, 3),
+                (2, true, 1),
+                (3, true, 1),
+                (4, true, 1)
+            ]
+        );
+        assert_eq!(
+            res.history[4].eliminated,
+            vec![Bits::from_str("10110").unwrap()]
+        );
+
+        let res = filter_by_criteria(&values, &CO2_CRITERIA);
+
+        assert_eq!(res.value.to_string(), "01010");
+        assert_eq!(res.history.len(), 3);
+    }
 
-        assert_eq!(230, res);
+    #[test]
+    fn test_parse_criteria() {
+        assert_eq!(BitCriteria::from_str("most:1:msb"), Ok(O2_CRITERIA));
+        assert_eq!(BitCriteria::from_str("least:0:msb"), Ok(CO2_CRITERIA));
+        assert_eq!(
+            BitCriteria::from_str("least:1:lsb"),
+            Ok(BitCriteria {
+                commonality: Commonality::Least,
+                tie_break: true,
+                order: BitOrder::LsbFirst,
+            })
+        );
+
+        assert!(BitCriteria::from_str("most:1").is_err());
+        assert!(BitCriteria::from_str("most:2:msb").is_err());
+        assert!(BitCriteria::from_str("some:1:msb").is_err());
     }
+
+    #[test]
+    fn test_filter_with_custom_criteria() {
+        let values = parse_values(&lines(TEST_INPUT));
+
+        let lsb_first = BitCriteria::from_str("most:1:lsb").unwrap();
+        let res = filter_by_criteria(&values, &lsb_first);
+
+        assert_eq!(res.value.to_string(), "11110");
+        assert_eq!(
+            res.history
+                .iter()
+                .map(|e| e.position)
+                .collect::<Vec<usize>>(),
+            vec![4, 3, 2, 1]
+        );
+
+        let zero_on_tie = BitCriteria {
+            commonality: Commonality::Most,
+            tie_break: false,
+            order: BitOrder::MsbFirst,
+        };
+        let res = filter_by_criteria(&parse_values(&lines("10\n01")), &zero_on_tie);
+
+        assert_eq!(res.value.to_string(), "01");
+    }
+
+    #[test]
+    fn test_filter_keeps_shared_bits() {
+        let values = parse_values(&lines("0010\n0111\n0100"));
+
+        let res = filter_by_criteria(&values, &CO2_CRITERIA);
+
+        assert!(!res.history[0].kept_bit);
+        assert!(res.history[0].eliminated.is_empty());
+        assert_eq!(res.value.to_string(), "0010");
+    }
+
+    #[test]
+    #[should_panic(expected = "Inconsistent width")]
+    fn test_inconsistent_width() {
+        parse_values(&lines("0101\n011"));
+    }
+
+    fn parsed(input: &str) -> Vec<Bits> {
+        parse_values(&lines(input))
+    }
+
+    example_test!(test_solve, parsed: TEST_INPUT => 198, 230);
 }
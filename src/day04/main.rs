@@ -1,103 +1,435 @@
-use std::fmt::Display;
+use std::collections::{HashMap, VecDeque};
+use std::env;
+use std::fmt::{self, Display};
 use std::time::Instant;
 
-use aoc_2021::get_input;
 use itertools::Itertools;
 
-fn parse_input(input: &[String]) -> (Vec<i16>, Vec<Vec<i16>>) {
-    let draw_numbers = input[0]
-        .split(',')
-        .map(|n| n.parse::<i16>().unwrap())
-        .collect();
-    let mut boards = Vec::new();
-    let mut current_board = Vec::new();
-
-    for l in input.iter().skip(2) {
-        if l.is_empty() {
-            boards.push(current_board);
-            current_board = Vec::new();
-            continue;
+use aoc_2021::get_input;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BingoBoard {
+    width: usize,
+    height: usize,
+    numbers: Vec<i32>,
+    marked: Vec<bool>,
+    diagonals: bool,
+}
+
+impl BingoBoard {
+    fn new(rows: &[Vec<i32>], diagonals: bool) -> Result<Self, String> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |r| r.len());
+
+        if width == 0 {
+            return Err("Empty board".to_string());
+        }
+
+        if rows.iter().any(|r| r.len() != width) {
+            return Err(format!("Rows of different lengths: {:?}", rows));
         }
 
-        let row: Vec<i16> = l
-            .split_whitespace()
-            .filter(|s| !s.is_empty())
-            .map(|n| n.parse().unwrap())
-            .collect();
-        current_board.extend(row);
+        Ok(Self {
+            width,
+            height,
+            numbers: rows.concat(),
+            marked: vec![false; width * height],
+            diagonals,
+        })
     }
 
-    boards.push(current_board);
+    fn rows(&self) -> impl Iterator<Item = Vec<usize>> + '_ {
+        (0..self.height).map(move |y| (0..self.width).map(|x| y * self.width + x).collect())
+    }
 
-    (draw_numbers, boards)
+    fn cols(&self) -> impl Iterator<Item = Vec<usize>> + '_ {
+        (0..self.width).map(move |x| (0..self.height).map(|y| y * self.width + x).collect())
+    }
+
+    fn diags(&self) -> Vec<Vec<usize>> {
+        if !self.diagonals || self.width != self.height {
+            return Vec::new();
+        }
+
+        let size = self.width;
+
+        vec![
+            (0..size).map(|i| i * size + i).collect(),
+            (0..size).map(|i| i * size + size - 1 - i).collect(),
+        ]
+    }
+
+    fn mark(&mut self, number: i32) -> bool {
+        let mut found = false;
+
+        for (n, m) in self.numbers.iter().zip(self.marked.iter_mut()) {
+            if *n == number {
+                *m = true;
+                found = true;
+            }
+        }
+
+        found
+    }
+
+    fn lines(&self) -> impl Iterator<Item = Vec<usize>> + '_ {
+        self.rows().chain(self.cols()).chain(self.diags())
+    }
+
+    fn has_won(&self) -> bool {
+        self.lines()
+            .any(|line| line.iter().all(|&idx| self.marked[idx]))
+    }
+
+    fn win_index(&self, positions: &HashMap<i32, usize>) -> Option<usize> {
+        self.lines()
+            .filter_map(|line| {
+                line.iter()
+                    .map(|&idx| positions.get(&self.numbers[idx]).copied())
+                    .collect::<Option<Vec<usize>>>()
+                    .and_then(|p| p.into_iter().max())
+            })
+            .min()
+    }
+
+    fn unmarked_sum(&self) -> i64 {
+        self.numbers
+            .iter()
+            .zip(self.marked.iter())
+            .filter(|(_, &m)| !m)
+            .map(|(&n, _)| n as i64)
+            .sum()
+    }
 }
 
-fn mark_boards(boards: &mut Vec<Vec<i16>>, number: i16) -> Vec<usize> {
-    let mut solved_boards = Vec::new();
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Win {
+    board: usize,
+    draw_index: usize,
+    number: i32,
+    score: i64,
+}
+
+struct BingoGame<'a> {
+    boards: Vec<BingoBoard>,
+    won: Vec<bool>,
+    draws: &'a [i32],
+    draw_index: usize,
+    pending: VecDeque<Win>,
+}
 
-    for (bnum, b) in boards.iter_mut().enumerate() {
-        if let Some((idx, _)) = b.iter().find_position(|&&n| n == number) {
-            b[idx] = -1;
-            if validate_row(b, idx / 5) || validate_col(b, idx % 5) {
-                solved_boards.push(bnum);
+impl<'a> BingoGame<'a> {
+    fn new(boards: Vec<BingoBoard>, draws: &'a [i32]) -> Self {
+        let won = vec![false; boards.len()];
+
+        Self {
+            boards,
+            won,
+            draws,
+            draw_index: 0,
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+impl Iterator for BingoGame<'_> {
+    type Item = Win;
+
+    fn next(&mut self) -> Option<Win> {
+        while self.pending.is_empty() {
+            let &number = self.draws.get(self.draw_index)?;
+
+            for (idx, board) in self.boards.iter_mut().enumerate() {
+                if self.won[idx] || !board.mark(number) || !board.has_won() {
+                    continue;
+                }
+
+                self.won[idx] = true;
+                self.pending.push_back(Win {
+                    board: idx,
+                    draw_index: self.draw_index,
+                    number,
+                    score: board.unmarked_sum() * number as i64,
+                });
             }
+
+            self.draw_index += 1;
         }
+
+        self.pending.pop_front()
+    }
+}
+
+fn get_draw_positions(draws: &[i32]) -> HashMap<i32, usize> {
+    let mut positions = HashMap::with_capacity(draws.len());
+
+    for (idx, &n) in draws.iter().enumerate() {
+        positions.entry(n).or_insert(idx);
     }
 
-    solved_boards
+    positions
 }
 
-fn validate_row(board: &[i16], row: usize) -> bool {
-    return board.iter().skip(row * 5).take(5).all(|&n| n == -1);
+fn get_win_indices(boards: &[BingoBoard], draws: &[i32]) -> Vec<Option<usize>> {
+    let positions = get_draw_positions(draws);
+
+    boards.iter().map(|b| b.win_index(&positions)).collect()
 }
 
-fn validate_col(board: &[i16], col: usize) -> bool {
-    return board.iter().skip(col).step_by(5).all(|&n| n == -1);
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Goal {
+    First,
+    Last,
 }
 
-fn part1(draw_numbers: &[i16], boards: &mut Vec<Vec<i16>>) -> u32 {
-    for n in draw_numbers {
-        let solved = mark_boards(boards, *n);
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Success,
+    Tie,
+    Failure,
+}
 
-        if solved.len() == 1 {
-            let res = boards[solved[0]]
-                .iter()
-                .filter(|&&n| n >= 0)
-                .map(|&n| n as u32)
-                .sum::<u32>();
-            return res * *n as u32;
+fn get_outcome(win_indices: &[Option<usize>], target: usize, goal: Goal) -> Outcome {
+    let target_idx = match win_indices[target] {
+        Some(idx) => idx,
+        None => return Outcome::Failure,
+    };
+
+    let others = win_indices
+        .iter()
+        .enumerate()
+        .filter(|&(b, _)| b != target)
+        .map(|(_, &idx)| idx);
+
+    let mut tie = false;
+
+    for other in others {
+        let beaten = match (goal, other) {
+            (Goal::First, None) => true,
+            (Goal::Last, None) => false,
+            (Goal::First, Some(other)) => target_idx < other,
+            (Goal::Last, Some(other)) => target_idx > other,
+        };
+
+        if other == Some(target_idx) {
+            tie = true;
+        } else if !beaten {
+            return Outcome::Failure;
+        }
+    }
+
+    if tie {
+        Outcome::Tie
+    } else {
+        Outcome::Success
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct SearchStats {
+    trials: usize,
+    successes: usize,
+    ties: usize,
+    failures: usize,
+    never_won: usize,
+    mean_win_index: f64,
+    best_draws: Option<Vec<i32>>,
+}
+
+impl SearchStats {
+    fn success_rate(&self) -> f64 {
+        if self.trials == 0 {
+            0.0
+        } else {
+            self.successes as f64 / self.trials as f64
         }
     }
+}
 
-    panic!("Non winning boards remaining");
+impl Display for SearchStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Trials: {}", self.trials)?;
+        writeln!(
+            f,
+            "Successes: {} ({:.2}%)",
+            self.successes,
+            self.success_rate() * 100.0
+        )?;
+        writeln!(f, "Ties: {}", self.ties)?;
+        writeln!(f, "Failures: {}", self.failures)?;
+        writeln!(f, "Never won: {}", self.never_won)?;
+        writeln!(f, "Mean win index: {:.2}", self.mean_win_index)?;
+
+        match &self.best_draws {
+            Some(draws) => writeln!(f, "Best draw order: {}", draws.iter().join(",")),
+            None => writeln!(f, "Best draw order: none"),
+        }
+    }
 }
 
-fn part2(draw_numbers: &[i16], boards: &mut Vec<Vec<i16>>) -> u32 {
-    for n in draw_numbers {
-        let solved = mark_boards(boards, *n);
+fn search_draw_orders<I>(boards: &[BingoBoard], orders: I, target: usize, goal: Goal) -> SearchStats
+where
+    I: IntoIterator<Item = Vec<i32>>,
+{
+    let mut trials = 0;
+    let mut successes = 0;
+    let mut ties = 0;
+    let mut failures = 0;
+    let mut never_won = 0;
+    let mut win_index_sum = 0;
+    let mut best: Option<(usize, Vec<i32>)> = None;
+
+    for draws in orders {
+        trials += 1;
+
+        let win_indices = get_win_indices(boards, &draws);
+
+        let target_idx = match win_indices[target] {
+            Some(idx) => idx,
+            None => {
+                never_won += 1;
+                continue;
+            }
+        };
+
+        win_index_sum += target_idx;
+
+        match get_outcome(&win_indices, target, goal) {
+            Outcome::Success => {
+                successes += 1;
 
-        for (idx, bnum) in solved.iter().enumerate() {
-            if boards.len() == 1 {
-                let res = boards[bnum - idx]
-                    .iter()
-                    .filter(|&&n| n >= 0)
-                    .map(|&n| n as u32)
-                    .sum::<u32>();
-                return res * *n as u32;
+                let is_better = match (&best, goal) {
+                    (None, _) => true,
+                    (Some((idx, _)), Goal::First) => target_idx < *idx,
+                    (Some((idx, _)), Goal::Last) => target_idx > *idx,
+                };
+
+                if is_better {
+                    best = Some((target_idx, draws));
+                }
             }
+            Outcome::Tie => ties += 1,
+            Outcome::Failure => failures += 1,
+        }
+    }
+
+    let nb_won = trials - never_won;
+
+    SearchStats {
+        trials,
+        successes,
+        ties,
+        failures,
+        never_won,
+        mean_win_index: if nb_won == 0 {
+            0.0
+        } else {
+            win_index_sum as f64 / nb_won as f64
+        },
+        best_draws: best.map(|(_, draws)| draws),
+    }
+}
+
+fn search_permutations(
+    boards: &[BingoBoard],
+    draws: &[i32],
+    target: usize,
+    goal: Goal,
+) -> SearchStats {
+    let orders = draws.iter().copied().permutations(draws.len());
+
+    search_draw_orders(boards, orders, target, goal)
+}
+
+struct XorShift(u64);
+
+impl XorShift {
+    fn new(seed: u64) -> Self {
+        Self(if seed == 0 { 0x9e3779b97f4a7c15 } else { seed })
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
 
-            boards.remove(bnum - idx);
+    fn shuffle<T>(&mut self, values: &mut [T]) {
+        for i in (1..values.len()).rev() {
+            let j = (self.next_u64() % (i as u64 + 1)) as usize;
+            values.swap(i, j);
         }
     }
+}
+
+fn search_shuffles(
+    boards: &[BingoBoard],
+    draws: &[i32],
+    target: usize,
+    goal: Goal,
+    trials: usize,
+    seed: u64,
+) -> SearchStats {
+    let mut rng = XorShift::new(seed);
+
+    let orders = (0..trials).map(|_| {
+        let mut draws = draws.to_vec();
+        rng.shuffle(&mut draws);
+        draws
+    });
+
+    search_draw_orders(boards, orders, target, goal)
+}
+
+fn parse_input(input: &[String]) -> (Vec<i32>, Vec<BingoBoard>) {
+    let draw_numbers = input[0]
+        .split(',')
+        .map(|n| n.parse::<i32>().unwrap())
+        .collect();
+
+    let boards = input[1..]
+        .split(|l| l.trim().is_empty())
+        .filter(|rows| !rows.is_empty())
+        .map(|rows| {
+            let rows = rows
+                .iter()
+                .map(|l| {
+                    l.split_whitespace()
+                        .map(|n| n.parse().unwrap())
+                        .collect::<Vec<i32>>()
+                })
+                .collect::<Vec<Vec<i32>>>();
+
+            match BingoBoard::new(&rows, false) {
+                Ok(board) => board,
+                Err(e) => panic!("{}", e),
+            }
+        })
+        .collect();
+
+    (draw_numbers, boards)
+}
+
+fn part1(draw_numbers: &[i32], boards: &[BingoBoard]) -> i64 {
+    match BingoGame::new(boards.to_vec(), draw_numbers).next() {
+        Some(win) => win.score,
+        None => panic!("Non winning boards remaining"),
+    }
+}
 
-    panic!("Non winning boards remaining");
+fn part2(draw_numbers: &[i32], boards: &[BingoBoard]) -> i64 {
+    match BingoGame::new(boards.to_vec(), draw_numbers).last() {
+        Some(win) => win.score,
+        None => panic!("Non winning boards remaining"),
+    }
 }
 
 fn solve(input: &[String]) -> (impl Display, impl Display) {
-    let (draw_numbers, mut boards) = parse_input(input);
+    let (draw_numbers, boards) = parse_input(input);
 
-    let p1 = part1(&draw_numbers, &mut boards);
-    let p2 = part2(&draw_numbers, &mut boards);
+    let p1 = part1(&draw_numbers, &boards);
+    let p2 = part2(&draw_numbers, &boards);
 
     (p1, p2)
 }
@@ -114,11 +446,49 @@ fn main() {
     println!("Part 1: {}", r1);
     println!("Part 2: {}", r2);
     println!("Duration: {:.3}ms", t);
+
+    let args = env::args().skip(1).collect::<Vec<String>>();
+
+    if let [goal, target, rest @ ..] = &args[..] {
+        let goal = match goal.as_str() {
+            "first" => Goal::First,
+            "last" => Goal::Last,
+            _ => panic!("Invalid goal: {}", goal),
+        };
+        let target = target.parse::<usize>().unwrap();
+        let (draw_numbers, boards) = parse_input(&input);
+
+        let win_indices = get_win_indices(&boards, &draw_numbers);
+        match win_indices[target] {
+            Some(idx) => println!("Board {} wins at draw {}", target, idx),
+            None => println!("Board {} never wins", target),
+        }
+
+        let stats = match rest.first().map(|s| s.as_str()) {
+            Some("all") => search_permutations(&boards, &draw_numbers, target, goal),
+            Some(n) => search_shuffles(
+                &boards,
+                &draw_numbers,
+                target,
+                goal,
+                n.parse().unwrap(),
+                2021,
+            ),
+            None => search_shuffles(&boards, &draw_numbers, target, goal, 10000, 2021),
+        };
+
+        print!("{}", stats);
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{parse_input, part1, part2};
+    use aoc_2021::{example_test, lines};
+
+    use crate::{
+        get_outcome, get_win_indices, parse_input, part1, part2, search_permutations,
+        search_shuffles, solve, BingoBoard, BingoGame, Goal, Outcome, Win,
+    };
 
     static INPUT: &str = "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1
 
@@ -142,10 +512,7 @@ mod tests {
 
     #[test]
     fn test_parse_input() {
-        let input = INPUT
-            .split("\n")
-            .map(|v| v.to_string())
-            .collect::<Vec<String>>();
+        let input = lines(INPUT);
 
         let res = parse_input(&input);
 
@@ -156,8 +523,12 @@ mod tests {
                 19, 3, 26, 1
             ]
         );
+        assert!(res.1.iter().all(|b| b.width == 5 && b.height == 5));
         assert_eq!(
-            res.1,
+            res.1
+                .iter()
+                .map(|b| b.numbers.clone())
+                .collect::<Vec<Vec<i32>>>(),
             vec![
                 vec![
                     22, 13, 17, 11, 0, 8, 2, 23, 4, 24, 21, 9, 14, 16, 7, 6, 10, 3, 18, 5, 1, 12,
@@ -177,27 +548,218 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        let input = INPUT
-            .split("\n")
-            .map(|v| v.to_string())
-            .collect::<Vec<String>>();
+        let input = lines(INPUT);
 
-        let (draw_numbers, mut boards) = parse_input(&input);
-        let res = part1(&draw_numbers, &mut boards);
+        let (draw_numbers, boards) = parse_input(&input);
+        let res = part1(&draw_numbers, &boards);
 
         assert_eq!(res, 4512);
     }
 
     #[test]
     fn test_part_2() {
-        let input = INPUT
-            .split("\n")
-            .map(|v| v.to_string())
-            .collect::<Vec<String>>();
+        let input = lines(INPUT);
 
-        let (draw_numbers, mut boards) = parse_input(&input);
-        let res = part2(&draw_numbers, &mut boards);
+        let (draw_numbers, boards) = parse_input(&input);
+        let res = part2(&draw_numbers, &boards);
 
         assert_eq!(res, 1924);
     }
+
+    #[test]
+    fn test_winners() {
+        let (draw_numbers, boards) = parse_input(&lines(INPUT));
+
+        let winners = BingoGame::new(boards, &draw_numbers).collect::<Vec<Win>>();
+
+        assert_eq!(
+            winners,
+            vec![
+                Win {
+                    board: 2,
+                    draw_index: 11,
+                    number: 24,
+                    score: 4512
+                },
+                Win {
+                    board: 0,
+                    draw_index: 13,
+                    number: 16,
+                    score: 2192
+                },
+                Win {
+                    board: 1,
+                    draw_index: 14,
+                    number: 13,
+                    score: 1924
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rectangular_board() {
+        let (draw_numbers, boards) = parse_input(&lines(
+            "1,2,3,4,5,6
+
+1 2 3 4
+5 6 7 8
+9 10 11 12
+
+1 5 9
+2 6 10",
+        ));
+
+        assert_eq!((boards[0].width, boards[0].height), (4, 3));
+        assert_eq!((boards[1].width, boards[1].height), (3, 2));
+
+        let winners = BingoGame::new(boards, &draw_numbers)
+            .map(|w| (w.board, w.draw_index))
+            .collect::<Vec<(usize, usize)>>();
+
+        assert_eq!(winners, vec![(1, 1), (0, 3)]);
+    }
+
+    #[test]
+    fn test_negative_numbers() {
+        let (draw_numbers, boards) = parse_input(&lines("-1,2,-3\n\n-1 5\n-3 2"));
+
+        let winners = BingoGame::new(boards, &draw_numbers).collect::<Vec<Win>>();
+
+        assert_eq!(
+            winners,
+            vec![Win {
+                board: 0,
+                draw_index: 2,
+                number: -3,
+                score: -3 * 5
+            }]
+        );
+    }
+
+    #[test]
+    fn test_diagonals() {
+        let rows = vec![vec![1, 2, 3], vec![4, 5, 6], vec![7, 8, 9]];
+        let draws = vec![3, 5, 7];
+
+        let plain = BingoBoard::new(&rows, false).unwrap();
+        assert_eq!(BingoGame::new(vec![plain], &draws).next(), None);
+
+        let diagonal = BingoBoard::new(&rows, true).unwrap();
+        assert_eq!(
+            BingoGame::new(vec![diagonal], &draws).next(),
+            Some(Win {
+                board: 0,
+                draw_index: 2,
+                number: 7,
+                score: 7 * (1 + 2 + 4 + 6 + 8 + 9)
+            })
+        );
+    }
+
+    #[test]
+    fn test_invalid_board() {
+        assert!(BingoBoard::new(&[vec![1, 2], vec![3]], false).is_err());
+        assert!(BingoBoard::new(&[], false).is_err());
+    }
+
+    #[test]
+    fn test_win_indices() {
+        let (draw_numbers, boards) = parse_input(&lines(INPUT));
+
+        assert_eq!(
+            get_win_indices(&boards, &draw_numbers),
+            vec![Some(13), Some(14), Some(11)]
+        );
+
+        let winners = BingoGame::new(boards.clone(), &draw_numbers)
+            .map(|w| (w.board, w.draw_index))
+            .collect::<Vec<(usize, usize)>>();
+        for (board, draw_index) in winners {
+            assert_eq!(
+                get_win_indices(&boards, &draw_numbers)[board],
+                Some(draw_index)
+            );
+        }
+
+        assert_eq!(get_win_indices(&boards, &draw_numbers[..11]), vec![None; 3]);
+    }
+
+    #[test]
+    fn test_win_indices_with_repeated_draws() {
+        let board = BingoBoard::new(&[vec![1, 2], vec![3, 4]], false).unwrap();
+
+        assert_eq!(get_win_indices(&[board], &[1, 1, 3, 2]), vec![Some(2)]);
+    }
+
+    #[test]
+    fn test_outcome() {
+        let win_indices = vec![Some(3), Some(5), None, Some(3)];
+
+        assert_eq!(get_outcome(&win_indices, 0, Goal::First), Outcome::Tie);
+        assert_eq!(get_outcome(&win_indices, 1, Goal::First), Outcome::Failure);
+        assert_eq!(get_outcome(&win_indices, 1, Goal::Last), Outcome::Failure);
+        assert_eq!(get_outcome(&win_indices, 2, Goal::First), Outcome::Failure);
+        assert_eq!(
+            get_outcome(&[Some(3), Some(5)], 1, Goal::Last),
+            Outcome::Success
+        );
+        assert_eq!(
+            get_outcome(&[Some(3), None], 0, Goal::First),
+            Outcome::Success
+        );
+    }
+
+    #[test]
+    fn test_search_permutations() {
+        let boards = vec![
+            BingoBoard::new(&[vec![1, 2], vec![11, 12]], false).unwrap(),
+            BingoBoard::new(&[vec![3, 4], vec![13, 14]], false).unwrap(),
+            BingoBoard::new(&[vec![1, 3], vec![15, 16]], false).unwrap(),
+        ];
+
+        let stats = search_permutations(&boards, &[1, 2, 3, 4], 2, Goal::First);
+
+        assert_eq!(stats.trials, 24);
+        assert_eq!(stats.successes, 4);
+        assert_eq!(stats.ties, 4);
+        assert_eq!(stats.failures, 16);
+        assert_eq!(stats.never_won, 0);
+        assert_eq!(
+            stats.best_draws.map(|d| d[..2].to_vec()).unwrap(),
+            vec![1, 3]
+        );
+
+        let stats = search_permutations(&boards, &[1, 2, 3], 1, Goal::First);
+
+        assert_eq!(stats.trials, 6);
+        assert_eq!(stats.successes, 0);
+        assert_eq!(stats.never_won, 6);
+        assert_eq!(stats.best_draws, None);
+    }
+
+    #[test]
+    fn test_search_shuffles() {
+        let (draw_numbers, boards) = parse_input(&lines(INPUT));
+
+        let stats = search_shuffles(&boards, &draw_numbers, 1, Goal::First, 200, 42);
+
+        assert_eq!(stats.trials, 200);
+        assert_eq!(
+            stats.trials,
+            stats.successes + stats.ties + stats.failures + stats.never_won
+        );
+        assert!(stats.successes > 0);
+
+        let draws = stats.best_draws.unwrap();
+        let win_indices = get_win_indices(&boards, &draws);
+        assert_eq!(get_outcome(&win_indices, 1, Goal::First), Outcome::Success);
+
+        assert_eq!(
+            search_shuffles(&boards, &draw_numbers, 1, Goal::First, 200, 42),
+            search_shuffles(&boards, &draw_numbers, 1, Goal::First, 200, 42)
+        );
+    }
+
+    example_test!(test_solve, INPUT => 4512, 1924);
 }
@@ -1,19 +1,44 @@
-use std::cmp::{self, Ordering};
-use std::fmt::Display;
+use std::collections::{HashMap, HashSet};
+use std::env;
+use std::fmt::{self, Display, Write};
+use std::fs;
+use std::path::Path;
 use std::time::Instant;
 
+use num::Integer;
+
 use aoc_2021::get_input;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct Point {
-    x: i32,
-    y: i32,
+    x: i64,
+    y: i64,
 }
 
 impl Point {
-    fn new(x: i32, y: i32) -> Self {
+    fn new(x: i64, y: i64) -> Self {
         Self { x, y }
     }
+
+    fn offset(&self, step: &Point, n: i64) -> Point {
+        Point::new(self.x + step.x * n, self.y + step.y * n)
+    }
+}
+
+fn cross(a: &Point, b: &Point) -> i128 {
+    a.x as i128 * b.y as i128 - a.y as i128 * b.x as i128
+}
+
+fn dot(a: &Point, b: &Point) -> i128 {
+    a.x as i128 * b.x as i128 + a.y as i128 * b.y as i128
+}
+
+type Line = (Point, i128);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Raster {
+    Lattice,
+    Bresenham,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -31,41 +56,131 @@ impl Vector {
         self.a.x == self.b.x || self.a.y == self.b.y
     }
 
-    fn get_all_points(&self) -> Vec<Point> {
+    fn get_step(&self) -> (Point, i64) {
         let dx = self.b.x - self.a.x;
         let dy = self.b.y - self.a.y;
 
-        if dx != 0 && dy != 0 && dx.abs() != dy.abs() {
-            panic!("Invalid vector");
+        let steps = dx.gcd(&dy);
+
+        if steps == 0 {
+            return (Point::new(0, 0), 0);
         }
 
-        let dx = match dx.cmp(&0) {
-            Ordering::Greater => 1,
-            Ordering::Less => -1,
-            Ordering::Equal => 0,
-        };
+        (Point::new(dx / steps, dy / steps), steps)
+    }
+
+    fn get_all_points(&self) -> Vec<Point> {
+        let (step, steps) = self.get_step();
+
+        (0..=steps).map(|n| self.a.offset(&step, n)).collect()
+    }
+
+    fn get_bresenham_points(&self) -> Vec<Point> {
+        let dx = (self.b.x - self.a.x).abs();
+        let dy = -(self.b.y - self.a.y).abs();
+        let sx = if self.a.x < self.b.x { 1 } else { -1 };
+        let sy = if self.a.y < self.b.y { 1 } else { -1 };
+
+        let mut points = Vec::new();
+        let mut p = self.a;
+        let mut err = dx + dy;
+
+        loop {
+            points.push(p);
+
+            if p == self.b {
+                return points;
+            }
+
+            let e2 = 2 * err;
+
+            if e2 >= dy {
+                err += dy;
+                p.x += sx;
+            }
+
+            if e2 <= dx {
+                err += dx;
+                p.y += sy;
+            }
+        }
+    }
+
+    fn get_points(&self, raster: Raster) -> Vec<Point> {
+        match raster {
+            Raster::Lattice => self.get_all_points(),
+            Raster::Bresenham => self.get_bresenham_points(),
+        }
+    }
+
+    fn contains(&self, p: &Point) -> bool {
+        let (step, steps) = self.get_step();
+        let diff = Point::new(p.x - self.a.x, p.y - self.a.y);
+
+        if steps == 0 {
+            return diff.x == 0 && diff.y == 0;
+        }
+
+        cross(&diff, &step) == 0
+            && (0..=steps as i128).contains(&(dot(&diff, &step) / dot(&step, &step)))
+    }
+
+    fn get_line(&self) -> Option<Line> {
+        let (step, steps) = self.get_step();
 
-        let dy = match dy.cmp(&0) {
-            Ordering::Greater => 1,
-            Ordering::Less => -1,
-            Ordering::Equal => 0,
+        if steps == 0 {
+            return None;
+        }
+
+        let step = if step.x < 0 || (step.x == 0 && step.y < 0) {
+            Point::new(-step.x, -step.y)
+        } else {
+            step
         };
 
-        let mut x = self.a.x;
-        let mut y = self.a.y;
+        Some((step, cross(&self.a, &step)))
+    }
 
-        let mut pts = Vec::new();
+    fn get_span(&self, step: &Point) -> (i128, i128) {
+        let norm = dot(step, step);
+        let t0 = dot(&self.a, step).div_euclid(norm);
+        let t1 = dot(&self.b, step).div_euclid(norm);
 
-        while x != self.b.x || y != self.b.y {
-            pts.push(Point { x, y });
+        (t0.min(t1), t0.max(t1))
+    }
+
+    fn get_crossing(&self, other: &Vector) -> Option<Point> {
+        let (step, steps) = self.get_step();
+        let (other_step, other_steps) = other.get_step();
+
+        if steps == 0 {
+            return other.contains(&self.a).then_some(self.a);
+        }
 
-            x += dx;
-            y += dy;
+        if other_steps == 0 {
+            return self.contains(&other.a).then_some(other.a);
         }
 
-        pts.push(Point { x, y });
+        let denom = cross(&step, &other_step);
 
-        pts
+        if denom == 0 {
+            return None;
+        }
+
+        let diff = Point::new(other.a.x - self.a.x, other.a.y - self.a.y);
+
+        let (s, rs) = cross(&diff, &other_step).div_rem(&denom);
+        let (t, rt) = cross(&diff, &step).div_rem(&denom);
+
+        if rs != 0
+            || rt != 0
+            || !(0..=steps as i128).contains(&s)
+            || !(0..=other_steps as i128).contains(&t)
+        {
+            return None;
+        }
+
+        Some(self.a.offset(&step, s as i64))
     }
 }
 
@@ -85,46 +200,332 @@ fn parse_input(input: &[String]) -> Vec<Vector> {
         .collect::<Vec<Vector>>()
 }
 
-fn part_1(vectors: &[Vector], board: &mut Vec<i32>, width: usize) -> usize {
-    for v in vectors.iter().filter(|v| v.is_line()) {
-        for p in v.get_all_points() {
-            board[p.x as usize * width + p.y as usize] += 1;
+fn get_board<'a, I: IntoIterator<Item = &'a Vector>>(
+    vectors: I,
+    raster: Raster,
+) -> HashMap<Point, u32> {
+    let mut board: HashMap<Point, u32> = HashMap::new();
+
+    for v in vectors {
+        for p in v.get_points(raster) {
+            *board.entry(p).or_insert(0) += 1;
         }
     }
 
-    board.iter().filter(|&&i| i > 1).count()
+    board
 }
 
-fn part_2(vectors: &[Vector], board: &mut Vec<i32>, width: usize) -> usize {
-    for v in vectors.iter().filter(|v| !v.is_line()) {
-        for p in v.get_all_points() {
-            board[p.x as usize * width + p.y as usize] += 1;
+fn count_overlaps_sparse<'a, I: IntoIterator<Item = &'a Vector>>(
+    vectors: I,
+    raster: Raster,
+) -> usize {
+    get_board(vectors, raster)
+        .values()
+        .filter(|&&i| i > 1)
+        .count()
+}
+
+fn get_shared_spans(spans: &[(i128, i128)]) -> Vec<(i128, i128)> {
+    let mut events = spans
+        .iter()
+        .flat_map(|&(start, end)| [(start, 1), (end + 1, -1)])
+        .collect::<Vec<(i128, i32)>>();
+    events.sort_unstable();
+
+    let mut shared = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (t, delta) in events {
+        if depth < 2 && depth + delta >= 2 {
+            start = t;
+        } else if depth >= 2 && depth + delta < 2 && t > start {
+            shared.push((start, t));
         }
+
+        depth += delta;
     }
 
-    board.iter().filter(|&&i| i > 1).count()
+    shared
 }
 
-fn solve(input: &[String]) -> (impl Display, impl Display) {
-    let vectors = parse_input(input);
+fn count_overlaps_analytic<'a, I: IntoIterator<Item = &'a Vector>>(vectors: I) -> usize {
+    let vectors = vectors.into_iter().collect::<Vec<&Vector>>();
+
+    let mut lines: HashMap<Line, Vec<(i128, i128)>> = HashMap::new();
 
-    let size_x = vectors
+    for v in &vectors {
+        if let Some(line) = v.get_line() {
+            lines.entry(line).or_default().push(v.get_span(&line.0));
+        }
+    }
+
+    let shared = lines
         .iter()
-        .map(|v| cmp::max(v.a.x, v.b.x))
-        .max()
-        .unwrap() as usize
-        + 1;
-    let size_y = vectors
+        .map(|(&line, spans)| (line, get_shared_spans(spans)))
+        .collect::<HashMap<Line, Vec<(i128, i128)>>>();
+
+    let get_shared_line = |p: &Point, v: &Vector| {
+        let line = v.get_line()?;
+        let t = dot(p, &line.0).div_euclid(dot(&line.0, &line.0));
+        let spans = &shared[&line];
+        let idx = spans.partition_point(|&(start, _)| start <= t);
+
+        (idx > 0 && t < spans[idx - 1].1).then_some(line)
+    };
+
+    let collinear = shared
+        .values()
+        .flatten()
+        .map(|(start, end)| end - start)
+        .sum::<i128>();
+
+    let mut crossings: HashMap<Point, HashSet<Line>> = HashMap::new();
+
+    for (i, a) in vectors.iter().enumerate() {
+        for b in &vectors[i + 1..] {
+            if let Some(p) = a.get_crossing(b) {
+                let lines = crossings.entry(p).or_default();
+                lines.extend(get_shared_line(&p, a));
+                lines.extend(get_shared_line(&p, b));
+            }
+        }
+    }
+
+    // A crossing already counted on k shared lines must be counted once overall
+    let corrections = crossings
+        .values()
+        .map(|lines| 1 - lines.len() as i128)
+        .sum::<i128>();
+
+    (collinear + corrections) as usize
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Counting {
+    Sparse(Raster),
+    Analytic,
+}
+
+impl Counting {
+    fn raster(&self) -> Raster {
+        match self {
+            Counting::Sparse(raster) => *raster,
+            Counting::Analytic => Raster::Lattice,
+        }
+    }
+}
+
+fn count_overlaps<'a, I: IntoIterator<Item = &'a Vector>>(vectors: I, counting: Counting) -> usize {
+    match counting {
+        Counting::Sparse(raster) => count_overlaps_sparse(vectors, raster),
+        Counting::Analytic => count_overlaps_analytic(vectors),
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Selection {
+    Axis,
+    All,
+}
+
+fn select(vectors: &[Vector], selection: Selection) -> impl Iterator<Item = &Vector> {
+    vectors
         .iter()
-        .map(|v| cmp::max(v.a.y, v.b.y))
-        .max()
-        .unwrap() as usize
-        + 1;
+        .filter(move |v| selection == Selection::All || v.is_line())
+}
+
+fn part_1(vectors: &[Vector], counting: Counting) -> usize {
+    count_overlaps(select(vectors, Selection::Axis), counting)
+}
+
+fn part_2(vectors: &[Vector], counting: Counting) -> usize {
+    count_overlaps(select(vectors, Selection::All), counting)
+}
+
+static HEAT_COLORS: [(u8, u8, u8); 5] = [
+    (0, 0, 0),
+    (40, 80, 200),
+    (220, 40, 40),
+    (250, 150, 30),
+    (255, 240, 120),
+];
+
+fn heat_color(count: u32) -> (u8, u8, u8) {
+    HEAT_COLORS[(count as usize).min(HEAT_COLORS.len() - 1)]
+}
+
+fn heat_char(count: u32) -> char {
+    match count {
+        0 => '.',
+        1..=9 => char::from_digit(count, 10).unwrap(),
+        _ => '+',
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Heatmap {
+    origin: Point,
+    width: usize,
+    height: usize,
+    counts: Vec<u32>,
+}
+
+impl Heatmap {
+    fn new(board: &HashMap<Point, u32>) -> Self {
+        if board.is_empty() {
+            return Heatmap {
+                origin: Point::new(0, 0),
+                width: 0,
+                height: 0,
+                counts: Vec::new(),
+            };
+        }
+
+        let min_x = board.keys().map(|p| p.x).min().unwrap();
+        let max_x = board.keys().map(|p| p.x).max().unwrap();
+        let min_y = board.keys().map(|p| p.y).min().unwrap();
+        let max_y = board.keys().map(|p| p.y).max().unwrap();
+
+        let width = (max_x - min_x + 1) as usize;
+        let height = (max_y - min_y + 1) as usize;
+
+        let mut counts = vec![0; width * height];
+
+        for (p, &count) in board {
+            counts[(p.y - min_y) as usize * width + (p.x - min_x) as usize] = count;
+        }
+
+        Heatmap {
+            origin: Point::new(min_x, min_y),
+            width,
+            height,
+            counts,
+        }
+    }
+
+    fn rows(&self) -> impl Iterator<Item = &[u32]> {
+        self.counts.chunks(self.width.max(1))
+    }
+
+    fn max(&self) -> u32 {
+        self.counts.iter().copied().max().unwrap_or(0)
+    }
+
+    fn to_pgm(&self) -> String {
+        let mut pgm = format!(
+            "P2\n{} {}\n{}\n",
+            self.width,
+            self.height,
+            self.max().max(1)
+        );
+
+        for row in self.rows() {
+            writeln!(
+                pgm,
+                "{}",
+                row.iter()
+                    .map(|c| c.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" ")
+            )
+            .unwrap();
+        }
+
+        pgm
+    }
+
+    fn to_ppm(&self) -> String {
+        let mut ppm = format!("P3\n{} {}\n255\n", self.width, self.height);
+
+        for row in self.rows() {
+            let pixels = row
+                .iter()
+                .map(|&c| {
+                    let (r, g, b) = heat_color(c);
+                    format!("{} {} {}", r, g, b)
+                })
+                .collect::<Vec<String>>();
+
+            writeln!(ppm, "{}", pixels.join(" ")).unwrap();
+        }
+
+        ppm
+    }
+
+    fn to_ansi(&self) -> String {
+        let mut ansi = String::new();
+
+        for row in self.rows() {
+            for &c in row {
+                let (r, g, b) = heat_color(c);
+                write!(ansi, "\x1b[38;2;{};{};{}m{}", r, g, b, heat_char(c)).unwrap();
+            }
+
+            ansi.push_str("\x1b[0m\n");
+        }
+
+        ansi
+    }
+}
+
+impl Display for Heatmap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows() {
+            writeln!(
+                f,
+                "{}",
+                row.iter().map(|&c| heat_char(c)).collect::<String>()
+            )?;
+        }
+
+        Ok(())
+    }
+}
 
-    let mut board = vec![0; (size_x * size_y) as usize];
+fn export_heatmap(heatmap: &Heatmap, selection: Selection, dir: &Path) {
+    let name = match selection {
+        Selection::Axis => "day05_axis",
+        Selection::All => "day05_all",
+    };
+
+    let files = [
+        (format!("{}.pgm", name), heatmap.to_pgm()),
+        (format!("{}.ppm", name), heatmap.to_ppm()),
+    ];
+
+    for (name, content) in files {
+        let path = dir.join(name);
+
+        match fs::write(&path, content) {
+            Ok(()) => println!("Wrote {}", path.display()),
+            Err(e) => panic!("Unable to write {}: {}", path.display(), e),
+        }
+    }
+}
+
+fn get_selection(arg: &str) -> Selection {
+    match arg {
+        "axis" => Selection::Axis,
+        "all" => Selection::All,
+        _ => panic!("Invalid selection: {}", arg),
+    }
+}
+
+fn get_counting(arg: &str) -> Counting {
+    match arg {
+        "analytic" => Counting::Analytic,
+        "sparse" => Counting::Sparse(Raster::Lattice),
+        "bresenham" => Counting::Sparse(Raster::Bresenham),
+        _ => panic!("Invalid counting method: {}", arg),
+    }
+}
+
+fn solve(input: &[String]) -> (impl Display, impl Display) {
+    let vectors = parse_input(input);
 
-    let p1 = part_1(&vectors, &mut board, size_y);
-    let p2 = part_2(&vectors, &mut board, size_y);
+    let p1 = part_1(&vectors, Counting::Analytic);
+    let p2 = part_2(&vectors, Counting::Analytic);
 
     (p1, p2)
 }
@@ -141,11 +542,40 @@ fn main() {
     println!("Part 1: {}", r1);
     println!("Part 2: {}", r2);
     println!("Duration: {:.3}ms", t);
+
+    let counting = match env::args().nth(1) {
+        Some(arg) => get_counting(&arg),
+        None => return,
+    };
+    let vectors = parse_input(&input);
+
+    println!(
+        "{:?} counting: part 1 {}, part 2 {}",
+        counting,
+        part_1(&vectors, counting),
+        part_2(&vectors, counting)
+    );
+
+    if let Some(selection) = env::args().nth(2) {
+        let selection = get_selection(&selection);
+        let board = get_board(select(&vectors, selection), counting.raster());
+        let heatmap = Heatmap::new(&board);
+
+        match env::args().nth(3) {
+            Some(dir) => export_heatmap(&heatmap, selection, Path::new(&dir)),
+            None => print!("{}", heatmap.to_ansi()),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{parse_input, part_1, part_2, Point, Vector};
+    use aoc_2021::{example_test, lines};
+
+    use crate::{
+        count_overlaps, get_board, parse_input, part_1, part_2, select, solve, Counting, Heatmap,
+        Point, Raster, Selection, Vector,
+    };
 
     static TEST_INPUT: &str = "0,9 -> 5,9
 8,0 -> 0,8
@@ -160,10 +590,7 @@ mod tests {
 
     #[test]
     fn test_parse_input() {
-        let input = TEST_INPUT
-            .split("\n")
-            .map(|v| v.to_string())
-            .collect::<Vec<String>>();
+        let input = lines(TEST_INPUT);
 
         let res = parse_input(&input);
 
@@ -240,31 +667,246 @@ mod tests {
     }
 
     #[test]
-    fn test_part_1() {
-        let input = TEST_INPUT
-            .split("\n")
-            .map(|v| v.to_string())
-            .collect::<Vec<String>>();
+    fn test_arbitrary_slopes() {
+        let v = Vector::new(Point::new(0, 0), Point::new(4, 2));
 
-        let mut board = vec![0; 100];
+        assert_eq!(
+            v.get_points(Raster::Lattice),
+            vec![Point::new(0, 0), Point::new(2, 1), Point::new(4, 2)]
+        );
+        assert_eq!(
+            v.get_points(Raster::Bresenham),
+            vec![
+                Point::new(0, 0),
+                Point::new(1, 1),
+                Point::new(2, 1),
+                Point::new(3, 2),
+                Point::new(4, 2),
+            ]
+        );
 
-        let res = part_1(&parse_input(&input), &mut board, 10);
+        let v = Vector::new(Point::new(3, 7), Point::new(0, 0));
+
+        assert_eq!(
+            v.get_points(Raster::Lattice),
+            vec![Point::new(3, 7), Point::new(0, 0)]
+        );
+        assert_eq!(v.get_points(Raster::Bresenham).len(), 8);
+
+        let v = Vector::new(Point::new(1, 1), Point::new(1, 1));
+
+        assert_eq!(v.get_points(Raster::Lattice), vec![Point::new(1, 1)]);
+        assert_eq!(v.get_points(Raster::Bresenham), vec![Point::new(1, 1)]);
+    }
+
+    #[test]
+    fn test_count_arbitrary_slopes() {
+        let vectors = parse_input(&lines("0,0 -> 6,3\n0,3 -> 6,0\n2,0 -> 2,3\n0,0 -> 4,2"));
+
+        assert_eq!(count_overlaps(&vectors, Counting::Analytic), 4);
+        assert_eq!(
+            count_overlaps(&vectors, Counting::Sparse(Raster::Lattice)),
+            4
+        );
+        assert_eq!(
+            count_overlaps(&vectors, Counting::Sparse(Raster::Bresenham)),
+            6
+        );
+    }
+
+    #[test]
+    fn test_part_1() {
+        let input = lines(TEST_INPUT);
+
+        let res = part_1(&parse_input(&input), Counting::Analytic);
 
         assert_eq!(res, 5);
     }
 
     #[test]
     fn test_part_2() {
-        let input = TEST_INPUT
-            .split("\n")
-            .map(|v| v.to_string())
-            .collect::<Vec<String>>();
-
-        let mut board = vec![0; 100];
+        let input = lines(TEST_INPUT);
 
-        part_1(&parse_input(&input), &mut board, 10);
-        let res = part_2(&parse_input(&input), &mut board, 10);
+        let res = part_2(&parse_input(&input), Counting::Analytic);
 
         assert_eq!(res, 12);
     }
+
+    #[test]
+    fn test_get_crossing() {
+        let v = |ax, ay, bx, by| Vector::new(Point::new(ax, ay), Point::new(bx, by));
+
+        assert_eq!(
+            v(0, 0, 4, 4).get_crossing(&v(4, 0, 0, 4)),
+            Some(Point::new(2, 2))
+        );
+        assert_eq!(v(0, 0, 3, 3).get_crossing(&v(3, 0, 0, 3)), None);
+        assert_eq!(
+            v(0, 0, 4, 4).get_crossing(&v(2, 0, 2, 9)),
+            Some(Point::new(2, 2))
+        );
+        assert_eq!(v(0, 0, 4, 4).get_crossing(&v(5, 0, 5, 9)), None);
+        assert_eq!(v(0, 0, 4, 0).get_crossing(&v(6, 0, 2, 0)), None);
+        assert_eq!(
+            v(2, 2, 2, 2).get_crossing(&v(0, 0, 4, 4)),
+            Some(Point::new(2, 2))
+        );
+        assert_eq!(
+            v(0, 0, 4, 4).get_crossing(&v(2, 2, 2, 2)),
+            Some(Point::new(2, 2))
+        );
+        assert_eq!(v(0, 0, 4, 4).get_crossing(&v(2, 3, 2, 3)), None);
+    }
+
+    #[test]
+    fn test_count_overlaps() {
+        let vectors = parse_input(&lines(TEST_INPUT));
+
+        for counting in [
+            Counting::Sparse(Raster::Lattice),
+            Counting::Sparse(Raster::Bresenham),
+            Counting::Analytic,
+        ] {
+            assert_eq!(part_1(&vectors, counting), 5);
+            assert_eq!(part_2(&vectors, counting), 12);
+        }
+
+        for (input, expected) in [
+            ("0,0 -> 4,0\n6,0 -> 2,0", 3),
+            ("0,0 -> 4,0\n6,0 -> 2,0\n3,-1 -> 3,1", 3),
+            ("0,0 -> 4,0\n6,0 -> 2,0\n5,-1 -> 5,1", 4),
+            ("0,0 -> 4,0\n1,0 -> 2,0\n2,0 -> 3,0", 3),
+            ("0,0 -> 4,0\n0,0 -> 4,0\n0,0 -> 4,0", 5),
+            ("5,5 -> 1,1\n3,3 -> 9,9\n7,3 -> 3,7", 3),
+            ("0,0 -> 6,3\n2,1 -> 8,4", 3),
+            ("2,2 -> 2,2\n0,0 -> 4,4\n2,2 -> 2,2", 1),
+            ("2,2 -> 2,2\n4,4 -> 4,4", 0),
+            ("5,4 -> 0,4\n1,4 -> 1,0\n1,4 -> 1,0\n0,4 -> 5,4", 10),
+        ] {
+            let vectors = parse_input(&lines(input));
+
+            assert_eq!(
+                count_overlaps(&vectors, Counting::Analytic),
+                count_overlaps(&vectors, Counting::Sparse(Raster::Lattice)),
+                "{}",
+                input
+            );
+            assert_eq!(count_overlaps(&vectors, Counting::Analytic), expected);
+        }
+    }
+
+    #[test]
+    fn test_huge_coordinates() {
+        let input = lines(
+            "1000000000,0 -> 1000000000,2000000000
+0,1000000000 -> 2000000000,1000000000
+0,0 -> 2000000000,2000000000
+999999990,999999990 -> 1000000010,1000000010",
+        );
+        let vectors = parse_input(&input);
+
+        assert_eq!(count_overlaps(&vectors, Counting::Analytic), 21);
+
+        let input = lines(
+            "0,0 -> 2000000000,0
+2000000000,0 -> 0,0",
+        );
+        let vectors = parse_input(&input);
+
+        assert_eq!(count_overlaps(&vectors, Counting::Analytic), 2000000001);
+
+        let input = lines(
+            "0,0 -> 4000000000,4000000000
+4000000000,4000000000 -> 0,0
+4000000000,0 -> 0,4000000000",
+        );
+        let vectors = parse_input(&input);
+
+        assert_eq!(count_overlaps(&vectors, Counting::Analytic), 4000000001);
+
+        let input = lines(
+            "0,0 -> 4000000000,3999999999
+4000000000,3999999999 -> 0,0
+4000000000,0 -> 0,3999999999",
+        );
+        let vectors = parse_input(&input);
+
+        assert_eq!(count_overlaps(&vectors, Counting::Analytic), 2);
+    }
+
+    fn heatmap(selection: Selection) -> Heatmap {
+        let vectors = parse_input(&lines(TEST_INPUT));
+
+        Heatmap::new(&get_board(select(&vectors, selection), Raster::Lattice))
+    }
+
+    #[test]
+    fn test_heatmap() {
+        assert_eq!(
+            heatmap(Selection::Axis).to_string(),
+            ".......1..
+..1....1..
+..1....1..
+.......1..
+.112111211
+..........
+..........
+..........
+..........
+222111....
+"
+        );
+
+        assert_eq!(
+            heatmap(Selection::All).to_string(),
+            "1.1....11.
+.111...2..
+..2.1.111.
+...1.2.2..
+.112313211
+...1.2....
+..1...1...
+.1.....1..
+1.......1.
+222111....
+"
+        );
+    }
+
+    #[test]
+    fn test_heatmap_offset() {
+        let vectors = parse_input(&lines("-2,5 -> 0,5\n-1,4 -> -1,6"));
+        let heatmap = Heatmap::new(&get_board(&vectors, Raster::Lattice));
+
+        assert_eq!(heatmap.origin, Point::new(-2, 4));
+        assert_eq!(heatmap.to_string(), ".1.\n121\n.1.\n");
+        assert_eq!(
+            Heatmap::new(&get_board(&[], Raster::Lattice)).to_string(),
+            ""
+        );
+    }
+
+    #[test]
+    fn test_heatmap_images() {
+        let heatmap = heatmap(Selection::Axis);
+
+        let pgm = heatmap.to_pgm();
+        assert!(pgm.starts_with("P2\n10 10\n2\n0 0 0 0 0 0 0 1 0 0\n"));
+        assert_eq!(pgm.lines().count(), 13);
+
+        let ppm = heatmap.to_ppm();
+        assert!(ppm.starts_with("P3\n10 10\n255\n0 0 0 0 0 0 "));
+        assert!(ppm
+            .lines()
+            .nth(7)
+            .unwrap()
+            .starts_with("0 0 0 40 80 200 40 80 200 220 40 40"));
+
+        let ansi = heatmap.to_ansi();
+        assert!(ansi.starts_with("\x1b[38;2;0;0;0m."));
+        assert!(ansi.contains("\x1b[38;2;220;40;40m2"));
+        assert_eq!(ansi.matches("\x1b[0m\n").count(), 10);
+    }
+
+    example_test!(test_solve, TEST_INPUT => 5, 12);
 }
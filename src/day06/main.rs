@@ -1,28 +1,266 @@
-use std::collections::VecDeque;
+use std::env;
 use std::fmt::Display;
+use std::str::FromStr;
 use std::time::Instant;
 
+use num::{BigUint, One, Zero};
+
 use aoc_2021::get_input;
 
-fn compute_population(state: &mut VecDeque<u64>, days: usize) -> u64 {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LifecycleModel {
+    newborn_timer: usize,
+    reset_timer: usize,
+    max_spawns: Option<usize>,
+}
+
+impl Default for LifecycleModel {
+    fn default() -> Self {
+        LifecycleModel {
+            newborn_timer: 8,
+            reset_timer: 6,
+            max_spawns: None,
+        }
+    }
+}
+
+impl FromStr for LifecycleModel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse = |v: &str| {
+            v.parse::<usize>()
+                .map_err(|_| format!("Invalid lifecycle model: {}", s))
+        };
+
+        match s.split(':').collect::<Vec<&str>>()[..] {
+            [newborn, reset] => LifecycleModel::new(parse(newborn)?, parse(reset)?, None),
+            [newborn, reset, spawns] => {
+                LifecycleModel::new(parse(newborn)?, parse(reset)?, Some(parse(spawns)?))
+            }
+            _ => Err(format!("Invalid lifecycle model: {}", s)),
+        }
+    }
+}
+
+impl LifecycleModel {
+    fn new(
+        newborn_timer: usize,
+        reset_timer: usize,
+        max_spawns: Option<usize>,
+    ) -> Result<Self, String> {
+        if max_spawns == Some(0) {
+            return Err("Fish must be allowed to spawn at least once".to_string());
+        }
+
+        Ok(LifecycleModel {
+            newborn_timer,
+            reset_timer,
+            max_spawns,
+        })
+    }
+
+    fn nb_timers(&self) -> usize {
+        self.newborn_timer.max(self.reset_timer) + 1
+    }
+
+    fn nb_generations(&self) -> usize {
+        self.max_spawns.unwrap_or(1)
+    }
+
+    fn size(&self) -> usize {
+        self.nb_generations() * self.nb_timers()
+    }
+
+    fn index(&self, spawns: usize, timer: usize) -> usize {
+        spawns * self.nb_timers() + timer
+    }
+
+    fn transitions(&self) -> Vec<(usize, usize)> {
+        let mut transitions = Vec::new();
+
+        for spawns in 0..self.nb_generations() {
+            for timer in 1..self.nb_timers() {
+                transitions.push((self.index(spawns, timer), self.index(spawns, timer - 1)));
+            }
+
+            let from = self.index(spawns, 0);
+            transitions.push((from, self.index(0, self.newborn_timer)));
+
+            let next = if self.max_spawns.is_some() {
+                spawns + 1
+            } else {
+                spawns
+            };
+
+            if next < self.nb_generations() {
+                transitions.push((from, self.index(next, self.reset_timer)));
+            }
+        }
+
+        transitions
+    }
+
+    fn initial_state(&self, input: &[u8]) -> Vec<u64> {
+        let mut state = vec![0u64; self.size()];
+
+        for &i in input {
+            if i as usize >= self.nb_timers() {
+                panic!("Invalid timer: {}", i);
+            }
+
+            state[self.index(0, i as usize)] += 1;
+        }
+
+        state
+    }
+
+    fn step(&self, state: &[u64]) -> Vec<u64> {
+        let mut next = vec![0u64; self.size()];
+
+        for (from, to) in self.transitions() {
+            next[to] += state[from];
+        }
+
+        next
+    }
+
+    fn transition_matrix(&self) -> Matrix {
+        let mut m = Matrix::zero(self.size());
+
+        for (from, to) in self.transitions() {
+            m.cells[to * m.size + from] += 1u32;
+        }
+
+        m
+    }
+}
+
+fn compute_population(model: &LifecycleModel, state: &mut [u64], days: usize) -> u64 {
     for _ in 0..days {
-        let nb_new = state.pop_front().unwrap();
-        state.push_back(nb_new);
-        state[6] += nb_new;
+        let next = model.step(state);
+        state.copy_from_slice(&next);
     }
 
     state.iter().sum::<u64>()
 }
 
-fn solve(input: &[u8]) -> (impl Display, impl Display) {
-    let mut state = VecDeque::from(vec![0u64; 9]);
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Matrix {
+    size: usize,
+    cells: Vec<BigUint>,
+}
 
-    for i in input {
-        state[*i as usize] += 1;
+impl Matrix {
+    fn zero(size: usize) -> Self {
+        Matrix {
+            size,
+            cells: vec![BigUint::zero(); size * size],
+        }
     }
 
-    let p1 = compute_population(&mut state, 80);
-    let p2 = compute_population(&mut state, 256 - 80);
+    fn identity(size: usize) -> Self {
+        let mut m = Matrix::zero(size);
+
+        for i in 0..size {
+            m.cells[i * size + i] = BigUint::one();
+        }
+
+        m
+    }
+
+    fn get(&self, row: usize, col: usize) -> &BigUint {
+        &self.cells[row * self.size + col]
+    }
+
+    fn mul(&self, other: &Matrix, modulus: Option<&BigUint>) -> Matrix {
+        let mut res = Matrix::zero(self.size);
+
+        for i in 0..self.size {
+            for k in 0..self.size {
+                let a = self.get(i, k);
+
+                if a.is_zero() {
+                    continue;
+                }
+
+                for j in 0..self.size {
+                    res.cells[i * self.size + j] += a * other.get(k, j);
+                }
+            }
+        }
+
+        if let Some(m) = modulus {
+            for c in res.cells.iter_mut() {
+                *c %= m;
+            }
+        }
+
+        res
+    }
+
+    fn pow(&self, mut exp: u64, modulus: Option<&BigUint>) -> Matrix {
+        let mut base = self.clone();
+        let mut res = Matrix::identity(self.size);
+
+        while exp > 0 {
+            if exp & 1 == 1 {
+                res = res.mul(&base, modulus);
+            }
+
+            exp >>= 1;
+
+            if exp > 0 {
+                base = base.mul(&base, modulus);
+            }
+        }
+
+        res
+    }
+
+    fn apply(&self, state: &[u64], modulus: Option<&BigUint>) -> Vec<BigUint> {
+        (0..self.size)
+            .map(|i| {
+                let v = state
+                    .iter()
+                    .enumerate()
+                    .map(|(j, &n)| self.get(i, j) * n)
+                    .sum::<BigUint>();
+
+                match modulus {
+                    Some(m) => v % m,
+                    None => v,
+                }
+            })
+            .collect()
+    }
+}
+
+fn compute_population_big(
+    model: &LifecycleModel,
+    state: &[u64],
+    days: u64,
+    modulus: Option<&BigUint>,
+) -> BigUint {
+    let population = model
+        .transition_matrix()
+        .pow(days, modulus)
+        .apply(state, modulus)
+        .into_iter()
+        .sum::<BigUint>();
+
+    match modulus {
+        Some(m) => population % m,
+        None => population,
+    }
+}
+
+fn solve(input: &[u8]) -> (impl Display, impl Display) {
+    let model = LifecycleModel::default();
+    let mut state = model.initial_state(input);
+
+    let p1 = compute_population(&model, &mut state, 80);
+    let p2 = compute_population(&model, &mut state, 256 - 80);
 
     (p1, p2)
 }
@@ -42,33 +280,161 @@ fn main() {
     println!("Part 1: {}", r1);
     println!("Part 2: {}", r2);
     println!("Duration: {:.3}μs", t);
+
+    if let Some(days) = env::args().nth(1) {
+        let days = days.parse().unwrap();
+        let modulus = env::args()
+            .nth(2)
+            .filter(|m| m != "-")
+            .map(|m| m.parse::<BigUint>().unwrap());
+        let model = match env::args().nth(3).map(|m| m.parse::<LifecycleModel>()) {
+            None => LifecycleModel::default(),
+            Some(Ok(model)) => model,
+            Some(Err(e)) => panic!("{}", e),
+        };
+
+        let start = Instant::now();
+
+        let state = model.initial_state(&input);
+        let population = compute_population_big(&model, &state, days, modulus.as_ref());
+
+        let t = start.elapsed().as_nanos() as f64 / 1000.0;
+
+        match &modulus {
+            Some(m) => println!("After {} days: {} (mod {})", days, population, m),
+            None => println!("After {} days: {}", days, population),
+        }
+        println!("Duration: {:.3}μs", t);
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::compute_population;
-    use std::collections::VecDeque;
+    use aoc_2021::{example_test, ints};
+
+    use crate::{compute_population, compute_population_big, solve, LifecycleModel};
+    use num::BigUint;
 
     static TEST_INPUT: &str = "3,4,3,1,2";
 
     #[test]
     fn test_compute_population() {
-        let input = TEST_INPUT
-            .split(',')
-            .map(|v| v.parse().unwrap())
-            .collect::<Vec<u8>>();
+        let input = ints::<u8>(TEST_INPUT);
+        let model = LifecycleModel::default();
 
-        let mut state = VecDeque::from(vec![0u64; 9]);
+        let mut state = model.initial_state(&input);
 
-        for i in input {
-            state[i as usize] += 1;
-        }
-
-        let res = compute_population(&mut state, 80);
+        let res = compute_population(&model, &mut state, 80);
 
         assert_eq!(res, 5934);
 
-        let res = compute_population(&mut state, 256 - 80);
+        let res = compute_population(&model, &mut state, 256 - 80);
         assert_eq!(res, 26984457539);
     }
+
+    #[test]
+    fn test_compute_population_big() {
+        let model = LifecycleModel::default();
+        let state = model.initial_state(&ints::<u8>(TEST_INPUT));
+
+        assert_eq!(
+            compute_population_big(&model, &state, 0, None),
+            BigUint::from(5u32)
+        );
+        assert_eq!(
+            compute_population_big(&model, &state, 18, None),
+            BigUint::from(26u32)
+        );
+        assert_eq!(
+            compute_population_big(&model, &state, 80, None),
+            BigUint::from(5934u32)
+        );
+        assert_eq!(
+            compute_population_big(&model, &state, 256, None),
+            BigUint::from(26984457539_u64)
+        );
+
+        for days in [1, 7, 9, 100, 300] {
+            let mut stepwise = state.clone();
+
+            assert_eq!(
+                compute_population_big(&model, &state, days, None),
+                BigUint::from(compute_population(&model, &mut stepwise, days as usize))
+            );
+        }
+    }
+
+    #[test]
+    fn test_compute_population_modulo() {
+        let model = LifecycleModel::default();
+        let state = model.initial_state(&ints::<u8>(TEST_INPUT));
+        let p = BigUint::from(1_000_000_007_u64);
+
+        assert_eq!(
+            compute_population_big(&model, &state, 256, Some(&p)),
+            BigUint::from(26984457539_u64 % 1_000_000_007)
+        );
+        assert_eq!(
+            compute_population_big(&model, &state, 2000, Some(&p)),
+            compute_population_big(&model, &state, 2000, None) % &p
+        );
+
+        let huge = compute_population_big(&model, &state, 1_000_000_000_000, Some(&p));
+        assert!(huge < p);
+    }
+
+    #[test]
+    fn test_lifecycle_model() {
+        assert_eq!("8:6".parse(), Ok(LifecycleModel::default()));
+        assert_eq!("1:1:2".parse(), LifecycleModel::new(1, 1, Some(2)));
+        assert!("1:1:0".parse::<LifecycleModel>().is_err());
+        assert!("1".parse::<LifecycleModel>().is_err());
+        assert!("a:b".parse::<LifecycleModel>().is_err());
+
+        let model = LifecycleModel::default();
+        assert_eq!(model.size(), 9);
+        assert_eq!(
+            model.step(&[1, 0, 0, 0, 0, 0, 0, 0, 0]),
+            vec![0, 0, 0, 0, 0, 0, 1, 0, 1]
+        );
+    }
+
+    #[test]
+    fn test_mortality() {
+        let model = LifecycleModel::new(1, 1, Some(2)).unwrap();
+        let mut state = model.initial_state(&[0]);
+
+        let populations = (0..8)
+            .map(|days| compute_population(&model, &mut state.clone(), days))
+            .collect::<Vec<u64>>();
+        assert_eq!(populations, vec![1, 2, 2, 3, 3, 5, 5, 8]);
+
+        let model = LifecycleModel::new(2, 2, Some(1)).unwrap();
+        state = model.initial_state(&[0, 1, 2, 2]);
+        assert_eq!(compute_population(&model, &mut state, 100), 4);
+    }
+
+    #[test]
+    fn test_models_agree() {
+        let input = ints::<u8>(TEST_INPUT);
+
+        for model in ["8:6", "4:4", "6:8", "2:5:3", "8:6:1", "8:6:4"] {
+            let model = model.parse::<LifecycleModel>().unwrap();
+            let state = model.initial_state(&input);
+
+            for days in [0, 1, 10, 57, 200] {
+                let mut stepwise = state.clone();
+
+                assert_eq!(
+                    compute_population_big(&model, &state, days, None),
+                    BigUint::from(compute_population(&model, &mut stepwise, days as usize)),
+                    "{:?} after {} days",
+                    model,
+                    days
+                );
+            }
+        }
+    }
+
+    example_test!(test_solve, ints: TEST_INPUT => 5934, 26984457539_u64);
 }
@@ -1,67 +1,231 @@
 use std::cmp;
-use std::fmt::Display;
+use std::env;
+use std::fmt::{Display, Write};
+use std::ops::RangeInclusive;
 use std::time::Instant;
 
 use aoc_2021::get_input;
 
-fn part_1(positions: &[i32]) -> i32 {
-    let t = *positions.get(positions.len() / 2).unwrap();
+type CostFn = fn(i64) -> i64;
 
-    let mut fuel = get_fuel_cost_linear(positions, t);
+#[derive(Debug, Clone, Copy)]
+enum Cost {
+    Linear,
+    Triangular,
+    Custom(CostFn),
+}
+
+impl Cost {
+    fn fuel(&self, distance: i64) -> i64 {
+        match self {
+            Cost::Linear => distance,
+            Cost::Triangular => distance * (distance + 1) / 2,
+            Cost::Custom(f) => f(distance),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Alignment {
+    position: i64,
+    fuel: i64,
+}
+
+fn get_fuel_cost(positions: &[i64], target: i64, cost: Cost) -> i64 {
+    positions
+        .iter()
+        .map(|&p| cost.fuel((p - target).abs()))
+        .sum()
+}
+
+fn align_at(positions: &[i64], target: i64, cost: Cost) -> Alignment {
+    Alignment {
+        position: target,
+        fuel: get_fuel_cost(positions, target, cost),
+    }
+}
+
+fn ternary_search(positions: &[i64], cost: Cost) -> Alignment {
+    let mut lo = *positions.iter().min().expect("No crabs to align");
+    let mut hi = *positions.iter().max().unwrap();
+
+    while hi - lo > 2 {
+        let m1 = lo + (hi - lo) / 3;
+        let m2 = hi - (hi - lo) / 3;
+
+        let f1 = get_fuel_cost(positions, m1, cost);
+        let f2 = get_fuel_cost(positions, m2, cost);
+
+        match f1.cmp(&f2) {
+            cmp::Ordering::Less => hi = m2 - 1,
+            cmp::Ordering::Greater => lo = m1 + 1,
+            cmp::Ordering::Equal => {
+                lo = m1;
+                hi = m2;
+            }
+        }
+    }
+
+    (lo..=hi)
+        .map(|t| align_at(positions, t, cost))
+        .min_by_key(|a| (a.fuel, a.position))
+        .unwrap()
+}
 
-    for i in 1..positions.len() {
-        let a = get_fuel_cost_linear(positions, t + i as i32);
-        let b = get_fuel_cost_linear(positions, t - i as i32);
+fn optimise(positions: &[i64], cost: Cost) -> Alignment {
+    match cost {
+        Cost::Linear => {
+            let mut sorted = positions.to_vec();
+            let mid = sorted.len() / 2;
+            let (_, &mut median, _) = sorted.select_nth_unstable(mid);
 
-        if a >= fuel && b >= fuel {
-            break;
+            align_at(positions, median, cost)
         }
+        Cost::Triangular => {
+            let n = positions.len() as i64;
+            assert!(n > 0, "No crabs to align");
+
+            let mean = positions.iter().sum::<i64>().div_euclid(n);
 
-        fuel = cmp::min(a, b);
+            [mean, mean + 1]
+                .iter()
+                .map(|&t| align_at(positions, t, cost))
+                .min_by_key(|a| (a.fuel, a.position))
+                .unwrap()
+        }
+        Cost::Custom(_) => ternary_search(positions, cost),
     }
+}
 
-    fuel
+#[derive(Debug)]
+struct CrabHistogram {
+    min: i64,
+    counts: Vec<i64>,
+    count_prefix: Vec<i64>,
+    sum_prefix: Vec<i64>,
+    total_count: i64,
+    total_sum: i64,
+    total_squares: i64,
 }
-fn part_2(positions: &[i32]) -> i32 {
-    let t = *positions.get(positions.len() / 2).unwrap();
 
-    let mut fuel = get_fuel_cost_increasing(positions, t);
+impl CrabHistogram {
+    fn new(positions: &[i64]) -> Self {
+        let min = *positions.iter().min().expect("No crabs to align");
+        let max = *positions.iter().max().unwrap();
+
+        let mut counts = vec![0; (max - min + 1) as usize];
 
-    for i in 1..positions.len() {
-        let a = get_fuel_cost_increasing(positions, t + i as i32);
-        let b = get_fuel_cost_increasing(positions, t - i as i32);
+        for &p in positions {
+            counts[(p - min) as usize] += 1;
+        }
+
+        let mut count_prefix = Vec::with_capacity(counts.len());
+        let mut sum_prefix = Vec::with_capacity(counts.len());
+        let (mut total_count, mut total_sum, mut total_squares) = (0, 0, 0);
+
+        for (x, &c) in counts.iter().enumerate() {
+            let x = x as i64;
+
+            total_count += c;
+            total_sum += c * x;
+            total_squares += c * x * x;
+
+            count_prefix.push(total_count);
+            sum_prefix.push(total_sum);
+        }
 
-        if a >= fuel && b >= fuel {
-            break;
+        CrabHistogram {
+            min,
+            counts,
+            count_prefix,
+            sum_prefix,
+            total_count,
+            total_sum,
+            total_squares,
         }
+    }
+
+    fn range(&self) -> RangeInclusive<i64> {
+        self.min..=self.min + self.counts.len() as i64 - 1
+    }
 
-        fuel = cmp::min(a, b);
+    fn linear_cost(&self, t: i64) -> i64 {
+        let (left_count, left_sum) = match t {
+            t if t < 0 => (0, 0),
+            t if t as usize >= self.counts.len() => (self.total_count, self.total_sum),
+            t => (self.count_prefix[t as usize], self.sum_prefix[t as usize]),
+        };
+
+        let right_count = self.total_count - left_count;
+        let right_sum = self.total_sum - left_sum;
+
+        t * left_count - left_sum + right_sum - t * right_count
     }
 
-    fuel
+    fn squared_cost(&self, t: i64) -> i64 {
+        self.total_squares - 2 * t * self.total_sum + t * t * self.total_count
+    }
+
+    fn cost(&self, target: i64, cost: Cost) -> i64 {
+        let t = target - self.min;
+
+        match cost {
+            Cost::Linear => self.linear_cost(t),
+            Cost::Triangular => (self.squared_cost(t) + self.linear_cost(t)) / 2,
+            Cost::Custom(_) => self
+                .counts
+                .iter()
+                .enumerate()
+                .filter(|(_, &c)| c > 0)
+                .map(|(x, &c)| c * cost.fuel((x as i64 - t).abs()))
+                .sum(),
+        }
+    }
+
+    fn cost_curve(&self, cost: Cost) -> Vec<Alignment> {
+        self.range()
+            .map(|position| Alignment {
+                position,
+                fuel: self.cost(position, cost),
+            })
+            .collect()
+    }
 }
 
-fn get_fuel_cost_linear(positions: &[i32], target: i32) -> i32 {
-    positions.iter().map(|&p| (p - target).abs()).sum()
+fn to_csv(curve: &[Alignment]) -> String {
+    let mut csv = String::from("position,fuel\n");
+
+    for a in curve {
+        writeln!(csv, "{},{}", a.position, a.fuel).unwrap();
+    }
+
+    csv
 }
 
-fn get_fuel_cost_increasing(positions: &[i32], target: i32) -> i32 {
-    positions
-        .iter()
-        .map(|&p| {
-            let x = (p - target).abs();
-            x * (x + 1) / 2
-        })
-        .sum()
+fn get_cost(name: &str) -> Cost {
+    match name {
+        "linear" => Cost::Linear,
+        "triangular" => Cost::Triangular,
+        "quadratic" => Cost::Custom(|d| d * d),
+        "cubic" => Cost::Custom(|d| d * d * d),
+        _ => panic!("Invalid cost: {}", name),
+    }
 }
 
-fn solve(input: &[String]) -> (impl Display, impl Display) {
-    let mut positions = input[0]
-        .split(',')
-        .map(|v| v.parse().unwrap())
-        .collect::<Vec<i32>>();
+fn part_1(positions: &[i64]) -> i64 {
+    optimise(positions, Cost::Linear).fuel
+}
+
+fn part_2(positions: &[i64]) -> i64 {
+    optimise(positions, Cost::Triangular).fuel
+}
 
-    positions.sort_unstable();
+fn parse_input(input: &[String]) -> Vec<i64> {
+    input[0].split(',').map(|v| v.parse().unwrap()).collect()
+}
+
+fn solve(input: &[String]) -> (impl Display, impl Display) {
+    let positions = parse_input(input);
 
     let p1 = part_1(&positions);
     let p2 = part_2(&positions);
@@ -81,20 +245,39 @@ fn main() {
     println!("Part 1: {}", r1);
     println!("Part 2: {}", r2);
     println!("Duration: {:.3}ms", t);
+
+    if let Some(cost) = env::args().nth(1) {
+        let positions = parse_input(&input);
+
+        if env::args().nth(2).as_deref() == Some("curve") {
+            let curve = CrabHistogram::new(&positions).cost_curve(get_cost(&cost));
+            print!("{}", to_csv(&curve));
+            return;
+        }
+
+        let alignment = optimise(&positions, get_cost(&cost));
+
+        println!(
+            "Best {} alignment: position {}, fuel {}",
+            cost, alignment.position, alignment.fuel
+        );
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{part_1, part_2};
+    use aoc_2021::{example_test, ints};
+
+    use crate::{
+        get_fuel_cost, optimise, part_1, part_2, solve, ternary_search, to_csv, Alignment, Cost,
+        CrabHistogram,
+    };
 
     static TEST_INPUT: &str = "16,1,2,0,4,2,7,1,2,14";
 
     #[test]
     fn test_part_1() {
-        let mut input = TEST_INPUT
-            .split(',')
-            .map(|v| v.parse().unwrap())
-            .collect::<Vec<i32>>();
+        let mut input = ints::<i64>(TEST_INPUT);
 
         input.sort();
 
@@ -105,10 +288,7 @@ mod tests {
 
     #[test]
     fn test_part_2() {
-        let mut input = TEST_INPUT
-            .split(',')
-            .map(|v| v.parse().unwrap())
-            .collect::<Vec<i32>>();
+        let mut input = ints::<i64>(TEST_INPUT);
 
         input.sort();
 
@@ -116,4 +296,127 @@ mod tests {
 
         assert_eq!(res, 168);
     }
+
+    #[test]
+    fn test_optimise() {
+        let input = ints::<i64>(TEST_INPUT);
+
+        assert_eq!(
+            optimise(&input, Cost::Linear),
+            Alignment {
+                position: 2,
+                fuel: 37
+            }
+        );
+        assert_eq!(
+            optimise(&input, Cost::Triangular),
+            Alignment {
+                position: 5,
+                fuel: 168
+            }
+        );
+        assert_eq!(
+            optimise(&input, Cost::Custom(|d| d * d)),
+            Alignment {
+                position: 5,
+                fuel: 291
+            }
+        );
+    }
+
+    #[test]
+    fn test_shortcuts_match_search() {
+        let datasets = [
+            ints::<i64>(TEST_INPUT),
+            vec![-5, 3, 3, 100, 42, -17],
+            vec![7],
+            vec![1, 2],
+            vec![0, 0, 0, 1000],
+        ];
+
+        for positions in &datasets {
+            for cost in [Cost::Linear, Cost::Triangular] {
+                assert_eq!(
+                    optimise(positions, cost).fuel,
+                    ternary_search(positions, cost).fuel
+                );
+            }
+
+            let quartic = Cost::Custom(|d| d * d * d * d);
+            let best = (-20..=1000)
+                .map(|t| positions.iter().map(|&p| (p - t).pow(4)).sum::<i64>())
+                .min()
+                .unwrap();
+            assert_eq!(optimise(positions, quartic).fuel, best);
+        }
+    }
+
+    #[test]
+    fn test_large_positions() {
+        let positions = vec![0, 2_000_000_000, 4_000_000_000];
+
+        assert_eq!(
+            optimise(&positions, Cost::Triangular),
+            Alignment {
+                position: 2_000_000_000,
+                fuel: 4_000_000_002_000_000_000
+            }
+        );
+    }
+
+    #[test]
+    fn test_histogram_cost() {
+        let datasets = [
+            ints::<i64>(TEST_INPUT),
+            vec![-5, 3, 3, 100, 42, -17],
+            vec![7],
+            vec![0, 0, 0, 1000],
+        ];
+        let costs = [Cost::Linear, Cost::Triangular, Cost::Custom(|d| d * d)];
+
+        for positions in &datasets {
+            let histogram = CrabHistogram::new(positions);
+
+            for cost in costs {
+                for target in -30..=1030 {
+                    assert_eq!(
+                        histogram.cost(target, cost),
+                        get_fuel_cost(positions, target, cost)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_cost_curve() {
+        let input = ints::<i64>(TEST_INPUT);
+        let histogram = CrabHistogram::new(&input);
+
+        for cost in [Cost::Linear, Cost::Triangular] {
+            let curve = histogram.cost_curve(cost);
+
+            assert_eq!(curve.len(), 17);
+            assert_eq!(curve[0].position, 0);
+            assert_eq!(curve[16].position, 16);
+            assert_eq!(
+                curve.iter().map(|a| a.fuel).min(),
+                Some(optimise(&input, cost).fuel)
+            );
+        }
+
+        let curve = histogram.cost_curve(Cost::Linear);
+        assert_eq!(curve[1].fuel, 41);
+        assert_eq!(curve[2].fuel, 37);
+        assert_eq!(curve[3].fuel, 39);
+        assert_eq!(curve[10].fuel, 71);
+
+        let curve = histogram.cost_curve(Cost::Triangular);
+        assert_eq!(curve[2].fuel, 206);
+        assert_eq!(curve[5].fuel, 168);
+
+        assert_eq!(to_csv(&curve[..2]), "position,fuel\n0,290\n1,242\n");
+    }
+
+    example_test!(test_solve, TEST_INPUT => 37, 168);
 }
@@ -1,141 +1,446 @@
-use std::collections::HashSet;
-use std::fmt::Display;
+use std::env;
+use std::fmt::{self, Display};
+use std::fs;
+use std::str::FromStr;
 use std::time::Instant;
 
 use aoc_2021::get_input;
 
-type Segments = Vec<char>;
+type Segments = u32;
+
+static DIGITS: [(char, &str); 10] = [
+    ('0', "abcefg"),
+    ('1', "cf"),
+    ('2', "acdeg"),
+    ('3', "acdfg"),
+    ('4', "bcdf"),
+    ('5', "abdfg"),
+    ('6', "abdefg"),
+    ('7', "acf"),
+    ('8', "abcdefg"),
+    ('9', "abcdfg"),
+];
+
+static HEX_LETTERS: [(char, &str); 6] = [
+    ('A', "abcdef"),
+    ('B', "bdefg"),
+    ('C', "abeg"),
+    ('D', "cdefg"),
+    ('E', "abdeg"),
+    ('F', "abde"),
+];
+
+fn parse_segments(s: &str) -> Result<Segments, String> {
+    let mut segments = 0;
+
+    for c in s.chars() {
+        if !c.is_ascii_lowercase() {
+            return Err(format!("Invalid segment: {}", c));
+        }
+
+        let bit = 1 << (c as u32 - 'a' as u32);
+
+        if segments & bit != 0 {
+            return Err(format!("Duplicate segment {} in {}", c, s));
+        }
+
+        segments |= bit;
+    }
+
+    Ok(segments)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct GlyphTable {
+    names: Vec<char>,
+    glyphs: Vec<Segments>,
+}
 
-fn parse_values(input: &[String]) -> Vec<(Vec<Segments>, Vec<Segments>)> {
-    let mut values = Vec::with_capacity(input.len());
+impl GlyphTable {
+    fn new(entries: &[(char, &str)]) -> Result<Self, String> {
+        let mut names = Vec::with_capacity(entries.len());
+        let mut glyphs = Vec::with_capacity(entries.len());
 
-    for i in input {
-        let (patterns, digits) = i.split_once(" | ").unwrap();
+        for &(name, shape) in entries {
+            let glyph = parse_segments(shape)?;
 
-        let patterns = patterns
-            .split_whitespace()
-            .map(|p| p.chars().collect())
-            .collect();
-        let digits = digits
-            .split_whitespace()
-            .map(|p| p.chars().collect())
-            .collect();
+            if names.contains(&name) {
+                return Err(format!("Duplicate glyph name: {}", name));
+            }
 
-        values.push((patterns, digits));
+            if glyphs.contains(&glyph) {
+                return Err(format!("Duplicate glyph shape: {}", shape));
+            }
+
+            names.push(name);
+            glyphs.push(glyph);
+        }
+
+        Ok(GlyphTable { names, glyphs })
     }
 
-    values
-}
+    fn digits() -> Self {
+        GlyphTable::new(&DIGITS).unwrap()
+    }
 
-fn get_pattern_diff(p1: &[char], p2: &[char]) -> Vec<char> {
-    let h1: HashSet<char> = HashSet::from_iter(p1.iter().cloned());
-    let h2: HashSet<char> = HashSet::from_iter(p2.iter().cloned());
+    fn hex() -> Self {
+        let entries = DIGITS
+            .iter()
+            .chain(HEX_LETTERS.iter())
+            .copied()
+            .collect::<Vec<(char, &str)>>();
 
-    let diff = h1.difference(&h2);
+        GlyphTable::new(&entries).unwrap()
+    }
 
-    Vec::from_iter(diff.cloned())
+    fn has_unique_length(&self, len: u32) -> bool {
+        self.glyphs.iter().filter(|g| g.count_ones() == len).count() == 1
+    }
 }
 
-fn is_pattern_same(p1: &[char], p2: &[char]) -> bool {
-    let h1: HashSet<char> = HashSet::from_iter(p1.iter().cloned());
-    let h2: HashSet<char> = HashSet::from_iter(p2.iter().cloned());
+impl FromStr for GlyphTable {
+    type Err = String;
 
-    h1 == h2
-}
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut entries = Vec::new();
 
-fn get_value(patterns: &[Segments], digits: &[Segments]) -> usize {
-    let pattern_1 = patterns.iter().find(|p| p.len() == 2).unwrap().clone();
-    let pattern_4 = patterns.iter().find(|p| p.len() == 4).unwrap().clone();
-    let pattern_7 = patterns.iter().find(|p| p.len() == 3).unwrap().clone();
-    let pattern_8 = patterns.iter().find(|p| p.len() == 7).unwrap().clone();
+        for line in s.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
 
-    let mut patterns: HashSet<Vec<char>> = HashSet::from_iter(patterns.iter().cloned());
-    patterns.remove(&pattern_1);
-    patterns.remove(&pattern_4);
-    patterns.remove(&pattern_7);
-    patterns.remove(&pattern_8);
+            match line.split_whitespace().collect::<Vec<&str>>()[..] {
+                [name, shape] if name.chars().count() == 1 => {
+                    entries.push((name.chars().next().unwrap(), shape))
+                }
+                _ => return Err(format!("Invalid glyph: {}", line)),
+            }
+        }
 
-    let pattern_6 = patterns
-        .iter()
-        .find(|p| p.len() == 6 && get_pattern_diff(&pattern_1, p).len() == 1)
-        .unwrap()
-        .clone();
-    patterns.remove(&pattern_6);
+        GlyphTable::new(&entries)
+    }
+}
 
-    let segment_c = *get_pattern_diff(&pattern_1, &pattern_6).first().unwrap();
+#[derive(Debug, PartialEq, Eq)]
+struct Note {
+    patterns: Vec<Segments>,
+    digits: Vec<Segments>,
+}
 
-    let pattern_5 = patterns
+fn parse_values(input: &[String]) -> Vec<Note> {
+    let parse = |s: &str| {
+        s.split_whitespace()
+            .map(|p| match parse_segments(p) {
+                Ok(segments) => segments,
+                Err(e) => panic!("{}", e),
+            })
+            .collect()
+    };
+
+    input
         .iter()
-        .find(|p| p.len() == 5 && !p.contains(&segment_c))
-        .unwrap()
-        .clone();
-    patterns.remove(&pattern_5);
+        .map(|i| {
+            let (patterns, digits) = i.split_once(" | ").unwrap();
 
-    let segment_f = *pattern_1.iter().find(|&&c| c != segment_c).unwrap();
+            Note {
+                patterns: parse(patterns),
+                digits: parse(digits),
+            }
+        })
+        .collect()
+}
 
-    let pattern_2 = patterns
-        .iter()
-        .find(|p| p.len() == 5 && !p.contains(&segment_f))
-        .unwrap()
-        .clone();
-    patterns.remove(&pattern_2);
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Wiring {
+    mapping: Vec<usize>,
+}
 
-    let segment_e = *get_pattern_diff(&pattern_2, &pattern_5)
+fn translate(mapping: &[usize], pattern: Segments) -> Segments {
+    mapping
         .iter()
-        .find(|&&c| c != segment_c)
-        .unwrap();
+        .enumerate()
+        .filter(|&(wire, _)| pattern & (1 << wire) != 0)
+        .fold(0, |acc, (_, &segment)| acc | 1 << segment)
+}
 
-    let pattern_9 = patterns
-        .iter()
-        .find(|p| p.len() == 6 && !p.contains(&segment_e))
-        .unwrap()
-        .clone();
-    patterns.remove(&pattern_9);
+impl Wiring {
+    fn translate(&self, pattern: Segments) -> Segments {
+        translate(&self.mapping, pattern)
+    }
+
+    fn decode(&self, pattern: Segments, glyphs: &[Segments]) -> Result<usize, String> {
+        if pattern >> self.mapping.len() != 0 {
+            return Err(format!(
+                "Pattern {} uses unknown wires",
+                segments_to_string(pattern)
+            ));
+        }
 
-    let pattern_3 = patterns.iter().find(|p| p.len() == 5).unwrap().clone();
-    patterns.remove(&pattern_3);
+        let translated = self.translate(pattern);
+
+        glyphs
+            .iter()
+            .position(|&g| g == translated)
+            .ok_or_else(|| format!("Unknown pattern: {}", segments_to_string(pattern)))
+    }
+}
+
+impl Display for Wiring {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let pairs = self
+            .mapping
+            .iter()
+            .enumerate()
+            .map(|(wire, &segment)| format!("{}->{}", segment_name(wire), segment_name(segment)))
+            .collect::<Vec<String>>();
+
+        write!(f, "{}", pairs.join(" "))
+    }
+}
 
-    assert_eq!(1, patterns.len());
-    let pattern_0 = patterns.iter().next().unwrap().clone();
+fn segment_name(idx: usize) -> char {
+    (b'a' + idx as u8) as char
+}
 
-    let patterns = vec![
-        pattern_0, pattern_1, pattern_2, pattern_3, pattern_4, pattern_5, pattern_6, pattern_7,
-        pattern_8, pattern_9,
-    ];
+fn segments_to_string(segments: Segments) -> String {
+    (0..Segments::BITS as usize)
+        .filter(|&i| segments & (1 << i) != 0)
+        .map(segment_name)
+        .collect()
+}
 
-    digits
+fn get_signature(patterns: &[Segments], bit: usize) -> Vec<u32> {
+    let mut signature = patterns
         .iter()
-        .map(|d| {
-            patterns
+        .filter(|&&p| p & (1 << bit) != 0)
+        .map(|p| p.count_ones())
+        .collect::<Vec<u32>>();
+
+    signature.sort_unstable();
+    signature
+}
+
+fn get_candidates(patterns: &[Segments], glyphs: &[Segments]) -> Result<Vec<Segments>, String> {
+    let nb_segments =
+        (Segments::BITS - glyphs.iter().fold(0, |acc, g| acc | g).leading_zeros()) as usize;
+    let all: Segments = (1 << nb_segments) - 1;
+
+    let mut candidates = vec![all; nb_segments];
+
+    for &p in patterns {
+        if p & !all != 0 {
+            return Err(format!(
+                "Pattern {} uses unknown wires",
+                segments_to_string(p)
+            ));
+        }
+
+        let matching = glyphs
+            .iter()
+            .filter(|g| g.count_ones() == p.count_ones())
+            .collect::<Vec<&Segments>>();
+
+        if matching.is_empty() {
+            return Err(format!(
+                "No glyph has the shape of {}",
+                segments_to_string(p)
+            ));
+        }
+
+        let allowed = matching.iter().fold(0, |acc, &&g| acc | g);
+        let required = matching.iter().fold(all, |acc, &&g| acc & g);
+
+        for (wire, c) in candidates.iter_mut().enumerate() {
+            if p & (1 << wire) != 0 {
+                *c &= allowed;
+            } else {
+                *c &= !required;
+            }
+        }
+    }
+
+    if patterns.len() == glyphs.len() {
+        for (wire, c) in candidates.iter_mut().enumerate() {
+            let signature = get_signature(patterns, wire);
+
+            *c &= (0..nb_segments)
+                .filter(|&segment| get_signature(glyphs, segment) == signature)
+                .fold(0, |acc, segment| acc | 1 << segment);
+        }
+    }
+
+    Ok(candidates)
+}
+
+struct Search<'a> {
+    patterns: &'a [Segments],
+    glyphs: &'a [Segments],
+    candidates: Vec<Segments>,
+    checks: Vec<Vec<Segments>>,
+    mapping: Vec<usize>,
+    solutions: Vec<Wiring>,
+}
+
+impl<'a> Search<'a> {
+    fn new(patterns: &'a [Segments], glyphs: &'a [Segments], candidates: Vec<Segments>) -> Self {
+        let mut checks = vec![Vec::new(); candidates.len()];
+
+        for &p in patterns {
+            if p != 0 {
+                checks[(Segments::BITS - 1 - p.leading_zeros()) as usize].push(p);
+            }
+        }
+
+        Search {
+            patterns,
+            glyphs,
+            candidates,
+            checks,
+            mapping: Vec::new(),
+            solutions: Vec::new(),
+        }
+    }
+
+    fn is_consistent(&self, wire: usize) -> bool {
+        self.checks[wire]
+            .iter()
+            .all(|&p| self.glyphs.contains(&translate(&self.mapping, p)))
+    }
+
+    fn run(&mut self, used: Segments) {
+        if self.solutions.len() > 1 {
+            return;
+        }
+
+        let wire = self.mapping.len();
+
+        if wire == self.candidates.len() {
+            let mut translated = self
+                .patterns
                 .iter()
-                .enumerate()
-                .find(|(_, p)| is_pattern_same(d, p))
-                .map(|(i, _)| i)
-                .unwrap()
+                .map(|&p| translate(&self.mapping, p))
+                .collect::<Vec<Segments>>();
+            translated.sort_unstable();
+            translated.dedup();
+
+            if translated.len() == self.patterns.len() {
+                self.solutions.push(Wiring {
+                    mapping: self.mapping.clone(),
+                });
+            }
+
+            return;
+        }
+
+        for segment in 0..self.candidates.len() {
+            let bit = 1 << segment;
+
+            if self.candidates[wire] & bit == 0 || used & bit != 0 {
+                continue;
+            }
+
+            self.mapping.push(segment);
+
+            if self.is_consistent(wire) {
+                self.run(used | bit);
+            }
+
+            self.mapping.pop();
+        }
+    }
+}
+
+fn solve_wiring(patterns: &[Segments], glyphs: &[Segments]) -> Result<Wiring, String> {
+    if patterns.len() > glyphs.len() {
+        return Err(format!(
+            "Expected at most {} patterns, got {}",
+            glyphs.len(),
+            patterns.len()
+        ));
+    }
+
+    let mut search = Search::new(patterns, glyphs, get_candidates(patterns, glyphs)?);
+    search.run(0);
+
+    match search.solutions.len() {
+        0 => Err("Inconsistent notes: no wiring matches every pattern".to_string()),
+        1 => Ok(search.solutions.pop().unwrap()),
+        _ => Err("Ambiguous notes: several wirings match every pattern".to_string()),
+    }
+}
+
+fn decode_positions(note: &Note, glyphs: &[Segments]) -> Result<Vec<usize>, String> {
+    let wiring = solve_wiring(&note.patterns, glyphs)?;
+
+    note.digits
+        .iter()
+        .map(|&d| wiring.decode(d, glyphs))
+        .collect()
+}
+
+fn decode(note: &Note, table: &GlyphTable) -> Result<String, String> {
+    let positions = decode_positions(note, &table.glyphs)?;
+
+    Ok(positions.into_iter().map(|i| table.names[i]).collect())
+}
+
+fn get_value(note: &Note, table: &GlyphTable) -> Result<usize, String> {
+    let radix = table.glyphs.len();
+
+    if radix < 2 {
+        return Err(format!(
+            "Glyph table of {} entries cannot be read as a number",
+            radix
+        ));
+    }
+
+    decode_positions(note, &table.glyphs)?
+        .into_iter()
+        .try_fold(0usize, |acc, d| {
+            acc.checked_mul(radix)
+                .and_then(|v| v.checked_add(d))
+                .ok_or_else(|| "Value overflows usize".to_string())
         })
-        .fold(0, |acc, d| acc * 10 + d)
 }
 
-fn part_1(values: &[(Vec<Segments>, Vec<Segments>)]) -> usize {
+fn get_table(name: &str) -> Result<GlyphTable, String> {
+    match name {
+        "digits" => Ok(GlyphTable::digits()),
+        "hex" => Ok(GlyphTable::hex()),
+        path => fs::read_to_string(path)
+            .map_err(|e| format!("{}: {}", path, e))?
+            .parse(),
+    }
+}
+
+fn part_1(values: &[Note], table: &GlyphTable) -> usize {
     values
         .iter()
         .map(|v| {
-            v.1.iter()
-                .filter(|&s| matches!(s.len(), 2 | 3 | 4 | 7))
+            v.digits
+                .iter()
+                .filter(|d| table.has_unique_length(d.count_ones()))
                 .count()
         })
         .sum()
 }
 
-fn part_2(values: &[(Vec<Segments>, Vec<Segments>)]) -> usize {
-    values.iter().map(|(p, d)| get_value(p, d)).sum()
+fn part_2(values: &[Note], table: &GlyphTable) -> usize {
+    values
+        .iter()
+        .map(|v| match get_value(v, table) {
+            Ok(value) => value,
+            Err(e) => panic!("{}", e),
+        })
+        .sum()
 }
 
 fn solve(input: &[String]) -> (impl Display, impl Display) {
     let segments = parse_values(input);
+    let table = GlyphTable::digits();
 
-    let p1 = part_1(&segments);
-    let p2 = part_2(&segments);
+    let p1 = part_1(&segments, &table);
+    let p2 = part_2(&segments, &table);
 
     (p1, p2)
 }
@@ -152,11 +457,30 @@ fn main() {
     println!("Part 1: {}", r1);
     println!("Part 2: {}", r2);
     println!("Duration: {:.3}ms", t);
+
+    if let Some(name) = env::args().nth(1) {
+        let table = match get_table(&name) {
+            Ok(table) => table,
+            Err(e) => panic!("{}", e),
+        };
+
+        for note in parse_values(&input) {
+            match decode(&note, &table) {
+                Ok(text) => println!("{}", text),
+                Err(e) => println!("Error: {}", e),
+            }
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{parse_values, part_1, part_2};
+    use aoc_2021::{example_test, lines};
+
+    use crate::{
+        decode, get_value, parse_segments, parse_values, part_1, part_2, solve, solve_wiring,
+        GlyphTable, Note, Segments, DIGITS,
+    };
 
     static TEST_INPUT: &str =
         "be cfbegad cbdgef fgaecd cgeb fdcge agebfd fecdb fabcd edb | fdgacbe cefdb cefbgd gcbe
@@ -172,25 +496,225 @@ gcafb gcf dcaebfg ecagb gf abcdeg gaef cafbge fdbac fegbdc | fgae cfgab fg bagce
 
     #[test]
     fn test_part_1() {
-        let input = TEST_INPUT
-            .split('\n')
-            .map(|v| v.to_string())
-            .collect::<Vec<String>>();
+        let input = lines(TEST_INPUT);
 
         let values = parse_values(&input);
 
-        assert_eq!(26, part_1(&values));
+        assert_eq!(26, part_1(&values, &GlyphTable::digits()));
     }
 
     #[test]
     fn test_part_2() {
-        let input = TEST_INPUT
-            .split('\n')
-            .map(|v| v.to_string())
-            .collect::<Vec<String>>();
+        let input = lines(TEST_INPUT);
 
         let values = parse_values(&input);
 
-        assert_eq!(61229, part_2(&values));
+        assert_eq!(61229, part_2(&values, &GlyphTable::digits()));
+    }
+
+    fn parse_patterns(s: &str) -> Vec<Segments> {
+        s.split_whitespace()
+            .map(|p| parse_segments(p).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_segments() {
+        assert_eq!(parse_segments("a"), Ok(0b1));
+        assert_eq!(parse_segments("gca"), Ok(0b1000101));
+        assert!(parse_segments("aa").is_err());
+        assert!(parse_segments("A").is_err());
     }
+
+    #[test]
+    fn test_solve_wiring() {
+        let glyphs = GlyphTable::digits().glyphs;
+        let patterns = parse_patterns("acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab");
+
+        let wiring = solve_wiring(&patterns, &glyphs).unwrap();
+
+        assert_eq!(wiring.to_string(), "a->c b->f c->g d->a e->b f->d g->e");
+        assert_eq!(wiring.mapping, vec![2, 5, 6, 0, 1, 3, 4]);
+
+        let note = &parse_values(&lines(
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb fcadb cdfeb cdbaf",
+        ))[0];
+        assert_eq!(get_value(note, &GlyphTable::digits()), Ok(5353));
+    }
+
+    #[test]
+    fn test_partial_notes() {
+        let glyphs = GlyphTable::digits().glyphs;
+
+        let patterns = parse_patterns("acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb");
+        let wiring = solve_wiring(&patterns, &glyphs).unwrap();
+        assert_eq!(wiring.to_string(), "a->c b->f c->g d->a e->b f->d g->e");
+
+        let patterns = parse_patterns("ab dab");
+        assert_eq!(
+            solve_wiring(&patterns, &glyphs),
+            Err("Ambiguous notes: several wirings match every pattern".to_string())
+        );
+    }
+
+    #[test]
+    fn test_invalid_notes() {
+        let glyphs = GlyphTable::digits().glyphs;
+
+        assert_eq!(
+            solve_wiring(&parse_patterns("ab cd"), &glyphs),
+            Err("Inconsistent notes: no wiring matches every pattern".to_string())
+        );
+        assert!(solve_wiring(&parse_patterns("abcdef ab abcdefgh"), &glyphs).is_err());
+        assert!(solve_wiring(&parse_patterns("abcdefg abcdefg"), &glyphs).is_err());
+        assert!(solve_wiring(&parse_patterns(&"ab ".repeat(11)), &glyphs).is_err());
+
+        let note = &parse_values(&lines(
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | cdfeb abcf",
+        ))[0];
+        assert_eq!(
+            get_value(note, &GlyphTable::digits()),
+            Err("Unknown pattern: abcf".to_string())
+        );
+
+        let note = &parse_values(&lines(
+            "acedgfb cdfbe gcdfa fbcad dab cefabd cdfgeb eafb cagedb ab | abh cdfbe",
+        ))[0];
+        assert_eq!(
+            get_value(note, &GlyphTable::digits()),
+            Err("Pattern abh uses unknown wires".to_string())
+        );
+    }
+
+    static FOURTEEN_SEGMENT: &str = "# a-f outer, g/h middle halves, i-n inner strokes
+A abcefgh
+B abcdhjm
+C adef
+D abcdjm
+E adefg
+F aefg
+H bcefgh
+I adjm
+J bcde
+K efgkn
+L def
+M bcefik
+N bcefin
+O abcdef
+P abefgh
+X ikln
+Y ikm
+Z adkl";
+
+    fn scramble(glyphs: &[Segments], wires: &[usize]) -> Vec<Segments> {
+        glyphs
+            .iter()
+            .map(|&g| {
+                wires
+                    .iter()
+                    .enumerate()
+                    .filter(|&(segment, _)| g & (1 << segment) != 0)
+                    .fold(0, |acc, (_, &wire)| acc | 1 << wire)
+            })
+            .collect()
+    }
+
+    fn scrambled_note(table: &GlyphTable, wires: &[usize], text: &str) -> Note {
+        let mut patterns = scramble(&table.glyphs, wires);
+        patterns.reverse();
+
+        let digits = text
+            .chars()
+            .map(|c| table.glyphs[table.names.iter().position(|&n| n == c).unwrap()])
+            .collect::<Vec<Segments>>();
+
+        Note {
+            patterns,
+            digits: scramble(&digits, wires),
+        }
+    }
+
+    #[test]
+    fn test_glyph_table() {
+        let table = FOURTEEN_SEGMENT.parse::<GlyphTable>().unwrap();
+
+        assert_eq!(table.names.len(), 18);
+        assert_eq!(table.glyphs[0], parse_segments("abcefgh").unwrap());
+        assert_eq!(GlyphTable::hex().glyphs.len(), 16);
+
+        assert!("A ab\nB ab".parse::<GlyphTable>().is_err());
+        assert!("A ab\nA bc".parse::<GlyphTable>().is_err());
+        assert!("AB ab".parse::<GlyphTable>().is_err());
+        assert!("A".parse::<GlyphTable>().is_err());
+    }
+
+    #[test]
+    fn test_hex_table() {
+        let table = GlyphTable::hex();
+        let note = scrambled_note(&table, &[3, 6, 0, 5, 1, 4, 2], "C0FFEE");
+
+        assert_eq!(decode(&note, &table), Ok("C0FFEE".to_string()));
+        assert_eq!(get_value(&note, &table), Ok(0xC0FFEE));
+
+        let partial = Note {
+            patterns: note.patterns[..12].to_vec(),
+            digits: note.digits.clone(),
+        };
+        assert_eq!(decode(&partial, &table), Ok("C0FFEE".to_string()));
+
+        let partial = Note {
+            patterns: note.patterns[..2].to_vec(),
+            digits: note.digits.clone(),
+        };
+        assert!(decode(&partial, &table).is_err());
+    }
+
+    #[test]
+    fn test_fourteen_segment_table() {
+        let table = FOURTEEN_SEGMENT.parse::<GlyphTable>().unwrap();
+        let wires = [9, 2, 13, 0, 7, 11, 4, 1, 12, 5, 3, 10, 8, 6];
+        let note = scrambled_note(&table, &wires, "JELLYPIX");
+
+        assert_eq!(decode(&note, &table), Ok("JELLYPIX".to_string()));
+        assert_eq!(get_value(&note, &table), Ok(5053852581));
+    }
+
+    #[test]
+    fn test_value_radix() {
+        let mut table = GlyphTable::hex();
+        let extra = (1..)
+            .filter(|g| !table.glyphs.contains(g))
+            .take(21)
+            .collect::<Vec<Segments>>();
+        for (name, glyph) in ('G'..='Z').chain(['!']).zip(extra) {
+            table.names.push(name);
+            table.glyphs.push(glyph);
+        }
+
+        let note = scrambled_note(&table, &[0, 1, 2, 3, 4, 5, 6], "Z!");
+        assert_eq!(get_value(&note, &table), Ok(35 * 37 + 36));
+
+        let table = GlyphTable::new(&[('0', "abc")]).unwrap();
+        let note = scrambled_note(&table, &[0, 1, 2, 3, 4, 5, 6], "00");
+        assert_eq!(
+            get_value(&note, &table),
+            Err("Glyph table of 1 entries cannot be read as a number".to_string())
+        );
+
+        let entries = ('A'..='J')
+            .zip(DIGITS.iter().map(|&(_, shape)| shape))
+            .collect::<Vec<(char, &str)>>();
+        let table = GlyphTable::new(&entries).unwrap();
+        let note = scrambled_note(&table, &[0, 1, 2, 3, 4, 5, 6], "AB");
+        assert_eq!(get_value(&note, &table), Ok(1));
+
+        let table = GlyphTable::digits();
+        let note = scrambled_note(&table, &[0, 1, 2, 3, 4, 5, 6], &"9".repeat(20));
+        assert_eq!(
+            get_value(&note, &table),
+            Err("Value overflows usize".to_string())
+        );
+    }
+
+    example_test!(test_solve, TEST_INPUT => 26, 61229);
 }
@@ -1,4 +1,8 @@
-use std::fmt::Display;
+use std::collections::HashSet;
+use std::env;
+use std::fmt::{Display, Write};
+use std::fs;
+use std::path::Path;
 use std::time::Instant;
 
 use aoc_2021::get_input;
@@ -36,45 +40,84 @@ fn find_low_points(values: &[Vec<u32>]) -> Vec<(usize, usize)> {
     low_points
 }
 
-fn get_basin(
-    values: &[Vec<u32>],
-    x: usize,
-    y: usize,
-    visited: &mut Vec<(usize, usize)>,
-) -> Vec<(usize, usize)> {
-    let mut basin = Vec::new();
+#[derive(Debug, PartialEq, Eq)]
+struct Basins {
+    labels: Vec<Vec<Option<usize>>>,
+    sizes: Vec<usize>,
+    low_points: Vec<(usize, usize)>,
+}
+
+fn get_neighbours(x: usize, y: usize, rows: usize, cols: usize) -> Vec<(usize, usize)> {
+    let mut neighbours = Vec::with_capacity(4);
+
+    if x > 0 {
+        neighbours.push((x - 1, y));
+    }
+
+    if x < rows - 1 {
+        neighbours.push((x + 1, y));
+    }
+
+    if y > 0 {
+        neighbours.push((x, y - 1));
+    }
 
-    if values[x][y] == 9 {
-        return basin;
+    if y < cols - 1 {
+        neighbours.push((x, y + 1));
     }
 
-    visited.push((x, y));
-    basin.push((x, y));
+    neighbours
+}
 
+fn label_basins(values: &[Vec<u32>]) -> Basins {
     let rows = values.len();
     let cols = values[0].len();
 
-    if x > 0 && !visited.contains(&(x - 1, y)) {
-        visited.push((x - 1, y));
-        basin.append(&mut get_basin(values, x - 1, y, visited));
-    }
+    let mut labels = vec![vec![None; cols]; rows];
+    let mut sizes = Vec::new();
+    let mut low_points = Vec::new();
+    let mut stack = Vec::new();
 
-    if x < rows - 1 && !visited.contains(&(x + 1, y)) {
-        visited.push((x + 1, y));
-        basin.append(&mut get_basin(values, x + 1, y, visited));
-    }
+    for x in 0..rows {
+        for y in 0..cols {
+            if values[x][y] == 9 || labels[x][y].is_some() {
+                continue;
+            }
 
-    if y > 0 && !visited.contains(&(x, y - 1)) {
-        visited.push((x, y - 1));
-        basin.append(&mut get_basin(values, x, y - 1, visited));
-    }
+            let id = sizes.len();
+            let mut size = 0;
+            let mut low_point = (x, y);
+
+            labels[x][y] = Some(id);
+            stack.push((x, y));
+
+            while let Some((cx, cy)) = stack.pop() {
+                size += 1;
+
+                if values[cx][cy] < values[low_point.0][low_point.1]
+                    || (values[cx][cy] == values[low_point.0][low_point.1] && (cx, cy) < low_point)
+                {
+                    low_point = (cx, cy);
+                }
+
+                for (nx, ny) in get_neighbours(cx, cy, rows, cols) {
+                    if values[nx][ny] != 9 && labels[nx][ny].is_none() {
+                        labels[nx][ny] = Some(id);
+                        stack.push((nx, ny));
+                    }
+                }
+            }
 
-    if y < cols - 1 && !visited.contains(&(x, y + 1)) {
-        visited.push((x, y + 1));
-        basin.append(&mut get_basin(values, x, y + 1, visited));
+            sizes.push(size);
+            low_points.push(low_point);
+        }
     }
 
-    basin
+    Basins {
+        labels,
+        sizes,
+        low_points,
+    }
 }
 
 fn part_1(values: &[Vec<u32>]) -> u32 {
@@ -87,28 +130,188 @@ fn part_1(values: &[Vec<u32>]) -> u32 {
 }
 
 fn part_2(values: &[Vec<u32>]) -> usize {
-    let low_points = find_low_points(values);
+    let mut sizes = label_basins(values).sizes;
+    sizes.sort_by(|a, b| b.cmp(a));
+
+    sizes[0..3].iter().product::<usize>()
+}
+
+type Color = (u8, u8, u8);
+
+static WALL_COLOR: Color = (30, 30, 30);
+static LOW_POINT_COLOR: Color = (255, 255, 255);
+static BASIN_COLORS: [Color; 6] = [
+    (230, 80, 80),
+    (80, 200, 90),
+    (70, 130, 230),
+    (240, 200, 60),
+    (190, 90, 220),
+    (60, 210, 210),
+];
+
+fn get_largest_basins(basins: &Basins, n: usize) -> Vec<usize> {
+    let mut ids = (0..basins.sizes.len()).collect::<Vec<usize>>();
+    ids.sort_by(|&a, &b| basins.sizes[b].cmp(&basins.sizes[a]));
+    ids.truncate(n);
+
+    ids
+}
+
+fn get_adjacent_basins(basins: &Basins) -> Vec<HashSet<usize>> {
+    let rows = basins.labels.len();
+    let cols = basins.labels[0].len();
+
+    let mut adjacent = vec![HashSet::new(); basins.sizes.len()];
+
+    for x in 0..rows {
+        for y in 0..cols {
+            if basins.labels[x][y].is_some() {
+                continue;
+            }
+
+            let around = (x.saturating_sub(1)..=(x + 1).min(rows - 1))
+                .flat_map(|nx| {
+                    (y.saturating_sub(1)..=(y + 1).min(cols - 1)).map(move |ny| (nx, ny))
+                })
+                .filter_map(|(nx, ny)| basins.labels[nx][ny])
+                .collect::<Vec<usize>>();
+
+            for &a in &around {
+                adjacent[a].extend(around.iter().filter(|&&b| b != a));
+            }
+        }
+    }
+
+    adjacent
+}
+
+fn get_color_indices(basins: &Basins) -> Vec<usize> {
+    let adjacent = get_adjacent_basins(basins);
+    let mut indices: Vec<usize> = Vec::with_capacity(adjacent.len());
+
+    for neighbours in &adjacent {
+        let used = neighbours
+            .iter()
+            .filter_map(|&n| indices.get(n).copied())
+            .collect::<HashSet<usize>>();
+
+        indices.push((0..).find(|i| !used.contains(i)).unwrap());
+    }
+
+    indices
+}
+
+fn get_basin_color(index: usize) -> Color {
+    if let Some(&color) = BASIN_COLORS.get(index) {
+        return color;
+    }
+
+    let hue = (index as f64 * 137.508) % 360.0 / 60.0;
+    let x = 1.0 - (hue % 2.0 - 1.0).abs();
+    let (r, g, b) = match hue as u32 {
+        0 => (1.0, x, 0.0),
+        1 => (x, 1.0, 0.0),
+        2 => (0.0, 1.0, x),
+        3 => (0.0, x, 1.0),
+        4 => (x, 0.0, 1.0),
+        _ => (1.0, 0.0, x),
+    };
+    let channel = |c: f64| (60.0 + c * 180.0) as u8;
+
+    (channel(r), channel(g), channel(b))
+}
+
+fn get_colors(values: &[Vec<u32>]) -> Vec<Vec<Color>> {
+    let basins = label_basins(values);
+    let largest = get_largest_basins(&basins, 3);
+    let indices = get_color_indices(&basins);
 
-    let basins = low_points
+    let mut colors = basins
+        .labels
         .iter()
-        .map(|&(x, y)| get_basin(values, x, y, &mut Vec::new()))
-        .collect::<Vec<Vec<(usize, usize)>>>();
+        .map(|row| {
+            row.iter()
+                .map(|label| match label {
+                    None => WALL_COLOR,
+                    Some(id) => {
+                        let (r, g, b) = get_basin_color(indices[*id]);
+
+                        if largest.contains(id) {
+                            (r, g, b)
+                        } else {
+                            (r / 3, g / 3, b / 3)
+                        }
+                    }
+                })
+                .collect::<Vec<Color>>()
+        })
+        .collect::<Vec<Vec<Color>>>();
 
-    let mut sizes = basins.iter().map(|b| b.len()).collect::<Vec<usize>>();
-    sizes.sort_by(|a, b| b.cmp(a));
+    for (x, y) in find_low_points(values) {
+        colors[x][y] = LOW_POINT_COLOR;
+    }
 
-    sizes[0..3].iter().product::<usize>()
+    colors
 }
 
-fn solve(input: &[String]) -> (impl Display, impl Display) {
-    let values = input
+fn to_ppm(colors: &[Vec<Color>]) -> String {
+    let mut ppm = format!("P3\n{} {}\n255\n", colors[0].len(), colors.len());
+
+    for row in colors {
+        let pixels = row
+            .iter()
+            .map(|(r, g, b)| format!("{} {} {}", r, g, b))
+            .collect::<Vec<String>>();
+
+        writeln!(ppm, "{}", pixels.join(" ")).unwrap();
+    }
+
+    ppm
+}
+
+fn to_ansi(values: &[Vec<u32>], colors: &[Vec<Color>]) -> String {
+    let mut ansi = String::new();
+
+    for (row, color_row) in values.iter().zip(colors) {
+        for (n, (r, g, b)) in row.iter().zip(color_row) {
+            write!(ansi, "\x1b[30;48;2;{};{};{}m{}", r, g, b, n).unwrap();
+        }
+
+        ansi.push_str("\x1b[0m\n");
+    }
+
+    ansi
+}
+
+fn render(values: &[Vec<u32>], dir: Option<&Path>) {
+    let colors = get_colors(values);
+
+    match dir {
+        Some(dir) => {
+            let path = dir.join("day09_basins.ppm");
+
+            match fs::write(&path, to_ppm(&colors)) {
+                Ok(()) => println!("Wrote {}", path.display()),
+                Err(e) => panic!("Unable to write {}: {}", path.display(), e),
+            }
+        }
+        None => print!("{}", to_ansi(values, &colors)),
+    }
+}
+
+fn parse_input(input: &[String]) -> Vec<Vec<u32>> {
+    input
         .iter()
         .map(|s| {
             s.chars()
                 .map(|c| c.to_digit(10).unwrap())
                 .collect::<Vec<u32>>()
         })
-        .collect::<Vec<Vec<u32>>>();
+        .collect::<Vec<Vec<u32>>>()
+}
+
+fn solve(input: &[String]) -> (impl Display, impl Display) {
+    let values = parse_input(input);
 
     let p1 = part_1(&values);
     let p2 = part_2(&values);
@@ -128,11 +331,25 @@ fn main() {
     println!("Part 1: {}", r1);
     println!("Part 2: {}", r2);
     println!("Duration: {:.3}ms", t);
+
+    if env::args().nth(1).as_deref() == Some("render") {
+        let dir = env::args().nth(2);
+
+        render(&parse_input(&input), dir.as_deref().map(Path::new));
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{part_1, part_2};
+    use aoc_2021::{example_test, lines};
+
+    use std::collections::HashSet;
+
+    use crate::{
+        find_low_points, get_basin_color, get_color_indices, get_colors, get_largest_basins,
+        label_basins, parse_input, part_1, part_2, solve, to_ansi, to_ppm, Color, BASIN_COLORS,
+        LOW_POINT_COLOR, WALL_COLOR,
+    };
 
     static TEST_INPUT: &str = "2199943210
 3987894921
@@ -142,29 +359,96 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        let input = TEST_INPUT
-            .split('\n')
-            .map(|v| {
-                v.chars()
-                    .map(|c| c.to_digit(10).unwrap())
-                    .collect::<Vec<u32>>()
-            })
-            .collect::<Vec<Vec<u32>>>();
+        let input = parse_input(&lines(TEST_INPUT));
 
         assert_eq!(15, part_1(&input));
     }
 
     #[test]
     fn test_part_2() {
-        let input = TEST_INPUT
-            .split('\n')
-            .map(|v| {
-                v.chars()
-                    .map(|c| c.to_digit(10).unwrap())
-                    .collect::<Vec<u32>>()
-            })
-            .collect::<Vec<Vec<u32>>>();
+        let input = parse_input(&lines(TEST_INPUT));
 
         assert_eq!(1134, part_2(&input));
     }
+
+    #[test]
+    fn test_label_basins() {
+        let input = parse_input(&lines(TEST_INPUT));
+
+        let basins = label_basins(&input);
+
+        assert_eq!(basins.sizes, vec![3, 9, 14, 9]);
+        assert_eq!(basins.low_points, vec![(0, 1), (0, 9), (2, 2), (4, 6)]);
+
+        let mut low_points = find_low_points(&input);
+        low_points.sort_unstable();
+        let mut basin_low_points = basins.low_points.clone();
+        basin_low_points.sort_unstable();
+        assert_eq!(low_points, basin_low_points);
+
+        let row = |x: usize| {
+            basins.labels[x]
+                .iter()
+                .map(|l| l.map_or('#', |id| (b'0' + id as u8) as char))
+                .collect::<String>()
+        };
+
+        assert_eq!(row(0), "00###11111");
+        assert_eq!(row(1), "0#222#1#11");
+        assert_eq!(row(2), "#22222#3#1");
+        assert_eq!(row(3), "22222#333#");
+        assert_eq!(row(4), "#2###33333");
+    }
+
+    #[test]
+    fn test_label_large_basin() {
+        let mut input = vec![vec![1; 600]; 600];
+        input[300] = vec![9; 600];
+        input[100][100] = 0;
+
+        let basins = label_basins(&input);
+
+        assert_eq!(basins.sizes, vec![300 * 600, 299 * 600]);
+        assert_eq!(basins.low_points, vec![(100, 100), (301, 0)]);
+    }
+
+    #[test]
+    fn test_render() {
+        let input = parse_input(&lines(TEST_INPUT));
+
+        assert_eq!(get_largest_basins(&label_basins(&input), 3), vec![2, 1, 3]);
+
+        let colors = get_colors(&input);
+        let (r, g, b) = BASIN_COLORS[0];
+
+        assert_eq!(colors[0][0], (r / 3, g / 3, b / 3));
+        assert_eq!(colors[0][1], LOW_POINT_COLOR);
+        assert_eq!(colors[0][2], WALL_COLOR);
+        assert_eq!(colors[0][5], BASIN_COLORS[0]);
+        assert_eq!(colors[3][0], BASIN_COLORS[1]);
+        assert_eq!(colors[4][9], BASIN_COLORS[2]);
+
+        let ppm = to_ppm(&colors);
+        assert!(ppm.starts_with("P3\n10 5\n255\n76 26 26 255 255 255 30 30 30 "));
+        assert_eq!(ppm.lines().count(), 8);
+
+        let ansi = to_ansi(&input, &colors);
+        assert!(ansi.starts_with("\x1b[30;48;2;76;26;26m2\x1b[30;48;2;255;255;255m1"));
+        assert_eq!(ansi.matches("\x1b[0m\n").count(), 5);
+    }
+
+    #[test]
+    fn test_color_indices() {
+        let basins = label_basins(&parse_input(&lines(TEST_INPUT)));
+        assert_eq!(get_color_indices(&basins), vec![0, 0, 1, 2]);
+
+        let basins = label_basins(&parse_input(&lines("09090\n99999\n09090")));
+        assert_eq!(get_color_indices(&basins), vec![0, 1, 0, 2, 3, 2]);
+
+        let colors = (0..50).map(get_basin_color).collect::<HashSet<Color>>();
+        assert_eq!(colors.len(), 50);
+        assert!(!colors.contains(&WALL_COLOR) && !colors.contains(&LOW_POINT_COLOR));
+    }
+
+    example_test!(test_solve, TEST_INPUT => 15, 1134);
 }
@@ -1,105 +1,269 @@
-use std::fmt::Display;
+use std::env;
+use std::fmt::{self, Display};
+use std::str::FromStr;
 use std::time::Instant;
 
 use aoc_2021::get_input;
 
-fn find_empty_chunk(chars: &[char]) -> Option<usize> {
-    for i in 0..chars.len() - 1 {
-        let a = chars[i];
-        let b = chars[i + 1];
+#[derive(Debug, PartialEq, Eq)]
+enum Lint {
+    Ok,
+    Corrupted {
+        position: usize,
+        expected: Option<char>,
+        found: char,
+    },
+    Incomplete {
+        completion: String,
+    },
+}
 
-        match (a, b) {
-            ('(', ')') | ('[', ']') | ('{', '}') | ('<', '>') => return Some(i),
-            _ => {}
+impl Display for Lint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Lint::Ok => write!(f, "ok"),
+            Lint::Corrupted {
+                position,
+                expected: Some(expected),
+                found,
+            } => write!(
+                f,
+                "column {}: expected {}, found {}",
+                position + 1,
+                expected,
+                found
+            ),
+            Lint::Corrupted {
+                position,
+                expected: None,
+                found,
+            } => write!(f, "column {}: unexpected {}", position + 1, found),
+            Lint::Incomplete { completion } => write!(f, "incomplete, missing {}", completion),
         }
     }
+}
 
-    None
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BracketPair {
+    open: char,
+    close: char,
+    corruption_score: usize,
+    completion_score: usize,
 }
 
-fn remove_empty_chunks(value: &str) -> Vec<char> {
-    let mut chars = value.chars().collect::<Vec<char>>();
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct BracketSpec {
+    pairs: Vec<BracketPair>,
+    completion_base: usize,
+    ignore_others: bool,
+}
 
-    while let Some(i) = find_empty_chunk(&chars) {
-        chars.remove(i);
-        chars.remove(i);
+impl Default for BracketSpec {
+    fn default() -> Self {
+        BracketSpec::new(
+            vec![
+                BracketPair::new('(', ')', 3, 1),
+                BracketPair::new('[', ']', 57, 2),
+                BracketPair::new('{', '}', 1197, 3),
+                BracketPair::new('<', '>', 25137, 4),
+            ],
+            5,
+            false,
+        )
+        .unwrap()
     }
+}
 
-    chars
+impl BracketPair {
+    fn new(open: char, close: char, corruption_score: usize, completion_score: usize) -> Self {
+        BracketPair {
+            open,
+            close,
+            corruption_score,
+            completion_score,
+        }
+    }
 }
 
-fn get_incorrect_char(value: &str) -> Option<char> {
-    let chars = remove_empty_chunks(value);
+impl FromStr for BracketSpec {
+    type Err = String;
 
-    chars
-        .iter()
-        .find(|c| matches!(c, ')' | ']' | '}' | '>'))
-        .cloned()
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("Invalid bracket pair in: {}", s);
+
+        let pairs = s
+            .split_whitespace()
+            .map(|token| {
+                let (brackets, scores) = token.split_once('=').unwrap_or((token, "0:0"));
+
+                let (open, close) = match brackets.chars().collect::<Vec<char>>()[..] {
+                    [open, close] => (open, close),
+                    _ => return Err(invalid()),
+                };
+
+                let (corruption, completion) = scores.split_once(':').ok_or_else(invalid)?;
+
+                Ok(BracketPair::new(
+                    open,
+                    close,
+                    corruption.parse().map_err(|_| invalid())?,
+                    completion.parse().map_err(|_| invalid())?,
+                ))
+            })
+            .collect::<Result<Vec<BracketPair>, String>>()?;
+
+        let completion_base = pairs.iter().map(|p| p.completion_score).max().unwrap_or(0) + 1;
+
+        BracketSpec::new(pairs, completion_base, false)
+    }
 }
 
-fn part_1(values: &[String]) -> usize {
-    let mut res = 0;
+impl BracketSpec {
+    fn new(
+        pairs: Vec<BracketPair>,
+        completion_base: usize,
+        ignore_others: bool,
+    ) -> Result<Self, String> {
+        let mut seen = Vec::new();
+
+        for p in &pairs {
+            for c in [p.open, p.close] {
+                if seen.contains(&c) {
+                    return Err(format!("Bracket {} is used more than once", c));
+                }
 
-    for val in values {
-        res += match get_incorrect_char(val) {
-            Some(')') => 3,
-            Some(']') => 57,
-            Some('}') => 1197,
-            Some('>') => 25137,
-            Some(_) => panic!("Invalid char"),
-            None => 0,
-        };
+                seen.push(c);
+            }
+        }
+
+        Ok(BracketSpec {
+            pairs,
+            completion_base,
+            ignore_others,
+        })
     }
 
-    res
-}
+    fn ignoring_others(self) -> Self {
+        BracketSpec {
+            ignore_others: true,
+            ..self
+        }
+    }
 
-fn get_missing_chars(value: &str) -> Vec<char> {
-    let chars = remove_empty_chunks(value);
+    fn get_closing(&self, c: char) -> Option<char> {
+        self.pairs.iter().find(|p| p.open == c).map(|p| p.close)
+    }
 
-    chars
-        .iter()
-        .rev()
-        .map(|c| match c {
-            '(' => ')',
-            '[' => ']',
-            '{' => '}',
-            '<' => '>',
-            _ => panic!("Invalid char: {}", c),
+    fn is_closing(&self, c: char) -> bool {
+        self.pairs.iter().any(|p| p.close == c)
+    }
+
+    fn get_corruption_score(&self, c: char) -> usize {
+        self.pairs
+            .iter()
+            .find(|p| p.close == c)
+            .map_or(0, |p| p.corruption_score)
+    }
+
+    fn get_completion_score(&self, completion: &str) -> usize {
+        completion.chars().fold(0, |acc, c| {
+            acc * self.completion_base
+                + self
+                    .pairs
+                    .iter()
+                    .find(|p| p.close == c)
+                    .map_or(0, |p| p.completion_score)
         })
-        .collect::<Vec<char>>()
+    }
+}
+
+fn lint(line: &str, spec: &BracketSpec) -> Lint {
+    let mut stack = Vec::new();
+
+    for (position, c) in line.chars().enumerate() {
+        if let Some(closing) = spec.get_closing(c) {
+            stack.push(closing);
+            continue;
+        }
+
+        if !spec.is_closing(c) {
+            if spec.ignore_others {
+                continue;
+            }
+
+            return Lint::Corrupted {
+                position,
+                expected: stack.last().copied(),
+                found: c,
+            };
+        }
+
+        match stack.pop() {
+            Some(expected) if expected == c => {}
+            expected => {
+                return Lint::Corrupted {
+                    position,
+                    expected,
+                    found: c,
+                }
+            }
+        }
+    }
+
+    if stack.is_empty() {
+        Lint::Ok
+    } else {
+        Lint::Incomplete {
+            completion: stack.iter().rev().collect(),
+        }
+    }
 }
 
-fn part_2(values: &[String]) -> usize {
-    let values = values
+fn part_1(values: &[String], spec: &BracketSpec) -> usize {
+    values
         .iter()
-        .filter(|s| get_incorrect_char(s).is_none())
-        .collect::<Vec<&String>>();
+        .map(|v| match lint(v, spec) {
+            Lint::Corrupted { found, .. } => spec.get_corruption_score(found),
+            _ => 0,
+        })
+        .sum()
+}
 
-    let mut points: Vec<usize> = values
+fn part_2(values: &[String], spec: &BracketSpec) -> usize {
+    let mut points = values
         .iter()
-        .map(|v| {
-            get_missing_chars(v).iter().fold(0, |acc, c| {
-                acc * 5
-                    + match c {
-                        ')' => 1,
-                        ']' => 2,
-                        '}' => 3,
-                        '>' => 4,
-                        _ => panic!("Invalid char: {}", c),
-                    }
-            })
+        .filter_map(|v| match lint(v, spec) {
+            Lint::Incomplete { completion } => Some(spec.get_completion_score(&completion)),
+            _ => None,
         })
-        .collect();
+        .collect::<Vec<usize>>();
 
     points.sort_unstable();
 
     points[points.len() / 2]
 }
 
+fn get_spec(args: &[String]) -> BracketSpec {
+    let spec = match args.first() {
+        Some(pairs) => match pairs.parse::<BracketSpec>() {
+            Ok(spec) => spec,
+            Err(e) => panic!("{}", e),
+        },
+        None => BracketSpec::default(),
+    };
+
+    match args.get(1).map(String::as_str) {
+        Some("ignore") => spec.ignoring_others(),
+        Some(arg) => panic!("Invalid option: {}", arg),
+        None => spec,
+    }
+}
+
 fn solve(input: &[String]) -> (impl Display, impl Display) {
-    let p1 = part_1(input);
-    let p2 = part_2(input);
+    let spec = BracketSpec::default();
+
+    let p1 = part_1(input, &spec);
+    let p2 = part_2(input, &spec);
 
     (p1, p2)
 }
@@ -116,11 +280,21 @@ fn main() {
     println!("Part 1: {}", r1);
     println!("Part 2: {}", r2);
     println!("Duration: {:.3}ms", t);
+
+    if env::args().nth(1).as_deref() == Some("lint") {
+        let spec = get_spec(&env::args().skip(2).collect::<Vec<String>>());
+
+        for (i, line) in input.iter().enumerate() {
+            println!("line {}: {}", i + 1, lint(line, &spec));
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::{part_1, part_2};
+    use aoc_2021::{example_test, lines};
+
+    use crate::{lint, part_1, part_2, solve, BracketPair, BracketSpec, Lint};
 
     static TEST_INPUT: &str = "[({(<(())[]>[[{[]{<()<>>
 [(()[<>])]({[<{<<[]>>(
@@ -135,21 +309,151 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        let input = TEST_INPUT
-            .split('\n')
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
+        let input = lines(TEST_INPUT);
 
-        assert_eq!(26397, part_1(&input));
+        assert_eq!(26397, part_1(&input, &BracketSpec::default()));
     }
 
     #[test]
     fn test_part_2() {
-        let input = TEST_INPUT
-            .split('\n')
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
+        let input = lines(TEST_INPUT);
+
+        assert_eq!(288957, part_2(&input, &BracketSpec::default()));
+    }
+
+    #[test]
+    fn test_lint() {
+        assert_eq!(lint("([]{<>})", &BracketSpec::default()), Lint::Ok);
+        assert_eq!(lint("", &BracketSpec::default()), Lint::Ok);
+        assert_eq!(
+            lint("{([(<{}[<>[]}>{[]{[(<()>", &BracketSpec::default()),
+            Lint::Corrupted {
+                position: 12,
+                expected: Some(']'),
+                found: '}'
+            }
+        );
+        assert_eq!(
+            lint("()>", &BracketSpec::default()),
+            Lint::Corrupted {
+                position: 2,
+                expected: None,
+                found: '>'
+            }
+        );
+        assert_eq!(
+            lint("[({(<(())[]>[[{[]{<()<>>", &BracketSpec::default()),
+            Lint::Incomplete {
+                completion: "}}]])})]".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_lint_display() {
+        assert_eq!(lint("()", &BracketSpec::default()).to_string(), "ok");
+        assert_eq!(
+            lint("[<>({}){}[([])<>]]<{[(}", &BracketSpec::default()).to_string(),
+            "column 23: expected ), found }"
+        );
+        assert_eq!(
+            lint(")", &BracketSpec::default()).to_string(),
+            "column 1: unexpected )"
+        );
+        assert_eq!(
+            lint("<{([", &BracketSpec::default()).to_string(),
+            "incomplete, missing ])}>"
+        );
+    }
 
-        assert_eq!(288957, part_2(&input));
+    #[test]
+    fn test_lint_long_line() {
+        let line = "(".repeat(100_000) + &")".repeat(100_000);
+
+        assert_eq!(lint(&line, &BracketSpec::default()), Lint::Ok);
     }
+
+    #[test]
+    fn test_parse_spec() {
+        let spec = "()=3:1 []=57:2 {}=1197:3 <>=25137:4"
+            .parse::<BracketSpec>()
+            .unwrap();
+        assert_eq!(spec, BracketSpec::default());
+
+        let spec = "() «»".parse::<BracketSpec>().unwrap();
+        assert_eq!(spec.pairs[1], BracketPair::new('«', '»', 0, 0));
+        assert_eq!(spec.completion_base, 1);
+
+        assert!("(".parse::<BracketSpec>().is_err());
+        assert!("()=3".parse::<BracketSpec>().is_err());
+        assert!("()=a:1".parse::<BracketSpec>().is_err());
+        assert!("() )(".parse::<BracketSpec>().is_err());
+    }
+
+    #[test]
+    fn test_custom_spec() {
+        let spec = "()=1:1 «»=10:2".parse::<BracketSpec>().unwrap();
+
+        assert_eq!(lint("(«»)", &spec), Lint::Ok);
+        assert_eq!(
+            lint("(«)", &spec),
+            Lint::Corrupted {
+                position: 2,
+                expected: Some('»'),
+                found: ')'
+            }
+        );
+        assert_eq!(
+            lint("((«", &spec),
+            Lint::Incomplete {
+                completion: "»))".to_string()
+            }
+        );
+
+        let input = vec!["(»".to_string(), "«(".to_string(), "((".to_string()];
+        assert_eq!(part_1(&input, &spec), 10);
+        assert_eq!(part_2(&input, &spec), 5);
+    }
+
+    #[test]
+    fn test_ignore_others() {
+        let spec = BracketSpec::default();
+        let code = "fn main() { let v = vec![1, (2)]; }";
+
+        assert_eq!(
+            lint(code, &spec),
+            Lint::Corrupted {
+                position: 0,
+                expected: None,
+                found: 'f'
+            }
+        );
+
+        let spec = spec.ignoring_others();
+
+        assert_eq!(lint(code, &spec), Lint::Ok);
+        assert_eq!(
+            lint("if (a[0) { x }", &spec),
+            Lint::Corrupted {
+                position: 7,
+                expected: Some(']'),
+                found: ')'
+            }
+        );
+        assert!(matches!(
+            lint("match x { Some(v) => {", &spec),
+            Lint::Corrupted { found: '>', .. }
+        ));
+
+        let spec = "() [] {}".parse::<BracketSpec>().unwrap().ignoring_others();
+
+        assert_eq!(
+            lint("match x { Some(v) => {", &spec),
+            Lint::Incomplete {
+                completion: "}}".to_string()
+            }
+        );
+    }
+
+    example_test!(test_solve, TEST_INPUT => 26397, 288957);
 }
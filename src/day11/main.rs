@@ -4,7 +4,7 @@ use std::time::Instant;
 use aoc_2021::get_input;
 use itertools::Itertools;
 
-fn tick(values: &mut Vec<u32>) -> usize {
+fn tick(values: &mut [u32]) -> usize {
     let mut flashes = Vec::new();
     let nb_values = values.len();
     let row_size = (nb_values as f32).sqrt() as usize;
@@ -127,7 +127,9 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
-    use crate::{parse_input, part_1, part_2};
+    use aoc_2021::{example_test, lines};
+
+    use crate::{parse_input, part_1, part_2, solve};
 
     static TEST_INPUT: &str = "5483143223
 2745854711
@@ -142,10 +144,7 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        let input = TEST_INPUT
-            .split('\n')
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
+        let input = lines(TEST_INPUT);
         let values = parse_input(&input);
 
         assert_eq!(204, part_1(&values, 10));
@@ -154,12 +153,11 @@ mod tests {
 
     #[test]
     fn test_part_2() {
-        let input = TEST_INPUT
-            .split('\n')
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
+        let input = lines(TEST_INPUT);
         let values = parse_input(&input);
 
         assert_eq!(195, part_2(&values));
     }
+
+    example_test!(test_solve, TEST_INPUT => 1656, 195);
 }
@@ -136,9 +136,6 @@ fn solve(input: &[String]) -> (impl Display, impl Display) {
     let p1 = get_nb_paths_with_single_visit(&neighbor_map, &Node::Start, &Vec::new());
     let p2 = get_nb_paths_with_double_visit(&neighbor_map, &Node::Start, &HashMap::new());
 
-    assert_eq!(p1, 5457);
-    assert_eq!(p2, 128506);
-
     (p1, p2)
 }
 
@@ -158,9 +155,11 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
+    use aoc_2021::{example_test, lines};
+
     use crate::{
-        get_nb_paths_with_double_visit, get_nb_paths_with_single_visit, parse_input, HashMap,
-        HashSet, Node,
+        get_nb_paths_with_double_visit, get_nb_paths_with_single_visit, parse_input, solve,
+        HashMap, HashSet, Node,
     };
 
     static TEST_INPUT_A: &str = "start-A
@@ -202,10 +201,7 @@ pj-fs
 start-RW";
 
     fn parsed_input(input: &str) -> HashMap<Node, HashSet<Node>> {
-        let input = input
-            .split('\n')
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
+        let input = lines(input);
 
         parse_input(&input)
     }
@@ -217,42 +213,36 @@ start-RW";
         let mut expected_neighbor_map = HashMap::new();
         expected_neighbor_map.insert(
             Node::Start,
-            vec![Node::from("A"), Node::from("b")]
+            [Node::from("A"), Node::from("b")]
                 .iter()
                 .cloned()
                 .collect::<HashSet<Node>>(),
         );
         expected_neighbor_map.insert(
             Node::Big("A".to_string()),
-            vec![Node::Start, Node::from("b"), Node::from("c"), Node::End]
+            [Node::Start, Node::from("b"), Node::from("c"), Node::End]
                 .iter()
                 .cloned()
                 .collect::<HashSet<Node>>(),
         );
         expected_neighbor_map.insert(
             Node::Small("b".to_string()),
-            vec![Node::Start, Node::from("A"), Node::from("d"), Node::End]
+            [Node::Start, Node::from("A"), Node::from("d"), Node::End]
                 .iter()
                 .cloned()
                 .collect::<HashSet<Node>>(),
         );
         expected_neighbor_map.insert(
             Node::Small("c".to_string()),
-            vec![Node::from("A")]
-                .iter()
-                .cloned()
-                .collect::<HashSet<Node>>(),
+            [Node::from("A")].iter().cloned().collect::<HashSet<Node>>(),
         );
         expected_neighbor_map.insert(
             Node::Small("d".to_string()),
-            vec![Node::from("b")]
-                .iter()
-                .cloned()
-                .collect::<HashSet<Node>>(),
+            [Node::from("b")].iter().cloned().collect::<HashSet<Node>>(),
         );
         expected_neighbor_map.insert(
             Node::End,
-            vec![Node::from("A"), Node::from("b")]
+            [Node::from("A"), Node::from("b")]
                 .iter()
                 .cloned()
                 .collect::<HashSet<Node>>(),
@@ -266,7 +256,7 @@ start-RW";
         let neighbor_map = parsed_input(TEST_INPUT_A);
 
         assert_eq!(
-            get_nb_paths_with_single_visit(&neighbor_map, &Node::Start, &vec![]),
+            get_nb_paths_with_single_visit(&neighbor_map, &Node::Start, &[]),
             10
         );
     }
@@ -275,7 +265,7 @@ start-RW";
         let neighbor_map = parsed_input(TEST_INPUT_B);
 
         assert_eq!(
-            get_nb_paths_with_single_visit(&neighbor_map, &Node::Start, &vec![]),
+            get_nb_paths_with_single_visit(&neighbor_map, &Node::Start, &[]),
             19
         );
     }
@@ -284,7 +274,7 @@ start-RW";
         let neighbor_map = parsed_input(TEST_INPUT_C);
 
         assert_eq!(
-            get_nb_paths_with_single_visit(&neighbor_map, &Node::Start, &vec![]),
+            get_nb_paths_with_single_visit(&neighbor_map, &Node::Start, &[]),
             226
         );
     }
@@ -316,4 +306,8 @@ start-RW";
             3509
         );
     }
+
+    example_test!(test_solve_a, TEST_INPUT_A => 10, 36);
+    example_test!(test_solve_b, TEST_INPUT_B => 19, 103);
+    example_test!(test_solve_c, TEST_INPUT_C => 226, 3509);
 }
@@ -150,6 +150,8 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
+    use aoc_2021::lines;
+
     use crate::{parse_input, part_1, part_2, Axis, Fold, Point};
 
     static TEST_INPUT: &str = "6,10
@@ -176,10 +178,7 @@ fold along x=5";
 
     #[test]
     fn test_parse_input() {
-        let input = TEST_INPUT
-            .split('\n')
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
+        let input = lines(TEST_INPUT);
         let (points, folds) = parse_input(&input);
 
         assert_eq!(
@@ -223,10 +222,7 @@ fold along x=5";
 
     #[test]
     fn test_part_1() {
-        let input = TEST_INPUT
-            .split('\n')
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
+        let input = lines(TEST_INPUT);
         let (points, folds) = parse_input(&input);
 
         assert_eq!(part_1(&points, &folds), 17)
@@ -234,10 +230,7 @@ fold along x=5";
 
     #[test]
     fn test_part_2() {
-        let input = TEST_INPUT
-            .split('\n')
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
+        let input = lines(TEST_INPUT);
         let (points, folds) = parse_input(&input);
 
         assert_eq!(
@@ -13,7 +13,7 @@ struct Rule<'a> {
     character: &'a str,
 }
 
-fn parse_input(input: &[String]) -> (HashMap<String, usize>, Vec<Rule>) {
+fn parse_input(input: &[String]) -> (HashMap<String, usize>, Vec<Rule<'_>>) {
     let pairs = input[0]
         .chars()
         .chain(iter::once(' '))
@@ -108,7 +108,9 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
-    use crate::{expand, get_diff_of_elements, parse_input, Rule};
+    use aoc_2021::{example_test, lines};
+
+    use crate::{expand, get_diff_of_elements, parse_input, solve, Rule};
     use std::collections::HashMap;
 
     static TEST_INPUT: &str = "NNCB
@@ -132,10 +134,7 @@ CN -> C";
 
     #[test]
     fn test_parse_input() {
-        let input = TEST_INPUT
-            .split('\n')
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
+        let input = lines(TEST_INPUT);
 
         let (pairs, rules) = parse_input(&input);
 
@@ -220,10 +219,7 @@ CN -> C";
 
     #[test]
     fn test_part_1() {
-        let input = TEST_INPUT
-            .split('\n')
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
+        let input = lines(TEST_INPUT);
 
         let (pairs, rules) = parse_input(&input);
 
@@ -234,10 +230,7 @@ CN -> C";
 
     #[test]
     fn test_part_2() {
-        let input = TEST_INPUT
-            .split('\n')
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
+        let input = lines(TEST_INPUT);
 
         let (pairs, rules) = parse_input(&input);
 
@@ -248,10 +241,7 @@ CN -> C";
 
     #[test]
     fn test_expand() {
-        let input = TEST_INPUT
-            .split('\n')
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
+        let input = lines(TEST_INPUT);
 
         let (pairs, rules) = parse_input(&input);
 
@@ -278,4 +268,6 @@ CN -> C";
 
         assert_eq!(result, expected);
     }
+
+    example_test!(test_solve, TEST_INPUT => 1588, 2188189693529_u64);
 }
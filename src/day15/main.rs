@@ -169,7 +169,9 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
-    use crate::{expand, get_cheapest_path, parse_input};
+    use aoc_2021::{example_test, lines};
+
+    use crate::{expand, get_cheapest_path, parse_input, solve};
 
     static TEST_INPUT: &str = "1163751742
 1381373672
@@ -184,10 +186,7 @@ mod tests {
 
     #[test]
     fn test_parse_input() {
-        let input = TEST_INPUT
-            .split('\n')
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
+        let input = lines(TEST_INPUT);
 
         let expected = vec![
             vec![1, 1, 6, 3, 7, 5, 1, 7, 4, 2],
@@ -206,10 +205,7 @@ mod tests {
 
     #[test]
     fn test_part_1() {
-        let input = TEST_INPUT
-            .split('\n')
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
+        let input = lines(TEST_INPUT);
 
         let tiles = parse_input(&input);
 
@@ -218,10 +214,7 @@ mod tests {
 
     #[test]
     fn test_part_2() {
-        let input = TEST_INPUT
-            .split('\n')
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
+        let input = lines(TEST_INPUT);
 
         let tiles = parse_input(&input);
 
@@ -230,10 +223,7 @@ mod tests {
 
     #[test]
     fn test_expand() {
-        let input = TEST_INPUT
-            .split('\n')
-            .map(|s| s.to_string())
-            .collect::<Vec<String>>();
+        let input = lines(TEST_INPUT);
 
         let input = parse_input(&input);
 
@@ -292,4 +282,6 @@ mod tests {
 
         assert_eq!(expand(&input, 5), expected);
     }
+
+    example_test!(test_solve, TEST_INPUT => 40, 315);
 }
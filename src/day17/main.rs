@@ -101,9 +101,12 @@ fn main() {
 
 #[cfg(test)]
 mod tests {
+    use aoc_2021::example_test;
     use rusttype::{point, vector, Rect};
 
-    use crate::{find_max_possible_height, get_number_of_valid_velocities, get_trajectory, parse};
+    use crate::{
+        find_max_possible_height, get_number_of_valid_velocities, get_trajectory, parse, solve,
+    };
 
     static TEST_INPUT: &str = "target area: x=20..30, y=-10..-5";
 
@@ -188,4 +191,6 @@ mod tests {
 
         assert_eq!(get_number_of_valid_velocities(&target_area), 112);
     }
+
+    example_test!(test_solve, String::from: TEST_INPUT => 45, 112);
 }
@@ -3,7 +3,7 @@ use std::time::Instant;
 
 use aoc_2021::get_input;
 
-fn solve(input: &[String]) -> (impl Display, impl Display) {
+fn solve(_input: &[String]) -> (impl Display, impl Display) {
     (0, 0)
 }
 
@@ -1,10 +1,149 @@
-use std::fmt::Display;
+use std::env;
+use std::fmt::{self, Display};
 use std::time::Instant;
 
 use aoc_2021::get_input;
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Cell {
+    Empty,
+    East,
+    South,
+}
+
+impl TryFrom<char> for Cell {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Cell, Self::Error> {
+        match c {
+            '.' => Ok(Cell::Empty),
+            '>' => Ok(Cell::East),
+            'v' => Ok(Cell::South),
+            _ => Err(format!("Invalid cell: {}", c)),
+        }
+    }
+}
+
+impl From<Cell> for char {
+    fn from(c: Cell) -> char {
+        match c {
+            Cell::Empty => '.',
+            Cell::East => '>',
+            Cell::South => 'v',
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Herd {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+}
+
+impl Herd {
+    fn next_position(&self, idx: usize, kind: Cell) -> usize {
+        let x = idx % self.width;
+        let y = idx / self.width;
+
+        match kind {
+            Cell::East => y * self.width + (x + 1) % self.width,
+            Cell::South => ((y + 1) % self.height) * self.width + x,
+            Cell::Empty => panic!("Empty cells don't move"),
+        }
+    }
+
+    fn move_herd(&mut self, kind: Cell) -> bool {
+        let moves = self
+            .cells
+            .iter()
+            .enumerate()
+            .filter(|(_, &c)| c == kind)
+            .map(|(idx, _)| (idx, self.next_position(idx, kind)))
+            .filter(|&(_, next)| self.cells[next] == Cell::Empty)
+            .collect::<Vec<(usize, usize)>>();
+
+        for &(idx, next) in &moves {
+            self.cells[idx] = Cell::Empty;
+            self.cells[next] = kind;
+        }
+
+        !moves.is_empty()
+    }
+
+    fn step(&mut self) -> bool {
+        let east_moved = self.move_herd(Cell::East);
+        let south_moved = self.move_herd(Cell::South);
+
+        east_moved || south_moved
+    }
+}
+
+impl Display for Herd {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.cells.chunks(self.width) {
+            let row: String = row.iter().map(|&c| char::from(c)).collect();
+            writeln!(f, "{}", row)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn parse_input(input: &[String]) -> Herd {
+    let rows = input
+        .iter()
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<&String>>();
+
+    let width = rows[0].len();
+    let height = rows.len();
+
+    let cells = rows
+        .iter()
+        .flat_map(|s| s.chars())
+        .map(|c| match Cell::try_from(c) {
+            Ok(cell) => cell,
+            Err(e) => panic!("{}", e),
+        })
+        .collect::<Vec<Cell>>();
+
+    assert_eq!(cells.len(), width * height, "Rows have different widths");
+
+    Herd {
+        width,
+        height,
+        cells,
+    }
+}
+
+fn get_state_after(herd: &Herd, steps: usize) -> Herd {
+    let mut herd = herd.clone();
+
+    for _ in 0..steps {
+        herd.step();
+    }
+
+    herd
+}
+
+fn find_stable_step(herd: &Herd) -> usize {
+    let mut herd = herd.clone();
+    let mut steps = 1;
+
+    while herd.step() {
+        steps += 1;
+    }
+
+    steps
+}
+
 fn solve(input: &[String]) -> (impl Display, impl Display) {
-    (0, 0)
+    let herd = parse_input(input);
+
+    let p1 = find_stable_step(&herd);
+
+    (p1, 0)
 }
 
 fn main() {
@@ -19,4 +158,99 @@ fn main() {
     println!("Part 1: {}", r1);
     println!("Part 2: {}", r2);
     println!("Duration: {:.3}ms", t);
+
+    if let Some(steps) = env::args().nth(1) {
+        let steps = steps.parse().unwrap();
+        let herd = get_state_after(&parse_input(&input), steps);
+
+        println!("After {} steps:", steps);
+        print!("{}", herd);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use aoc_2021::{example_test, lines};
+
+    use crate::{find_stable_step, get_state_after, parse_input, solve, Herd};
+
+    static TEST_INPUT: &str = "v...>>.vv>
+.vv>>.vv..
+>>.>v>...v
+>>v>>.>.v.
+v>v.vv.v..
+>.>>..v...
+.vv..>.>v.
+v.v..>>v.v
+....v..v.>";
+
+    fn parsed_input(input: &str) -> Herd {
+        let input = lines(input);
+
+        parse_input(&input)
+    }
+
+    #[test]
+    fn test_parse_input() {
+        let herd = parsed_input(TEST_INPUT);
+
+        assert_eq!(herd.width, 10);
+        assert_eq!(herd.height, 9);
+        assert_eq!(herd.to_string(), format!("{}\n", TEST_INPUT));
+    }
+
+    #[test]
+    fn test_step_moves_east_before_south() {
+        let mut herd =
+            parsed_input("...>...\n.......\n......>\nv.....>\n......>\n.......\n..vvv..");
+
+        herd.step();
+
+        assert_eq!(
+            herd.to_string(),
+            "..vv>..\n.......\n>......\nv.....>\n>......\n.......\n....v..\n"
+        );
+    }
+
+    #[test]
+    fn test_get_state_after() {
+        let herd = parsed_input(TEST_INPUT);
+
+        assert_eq!(
+            get_state_after(&herd, 1).to_string(),
+            "....>.>v.>
+v.v>.>v.v.
+>v>>..>v..
+>>v>v>.>.v
+.>v.v...v.
+v>>.>vvv..
+..v...>>..
+vv...>>vv.
+>.v.v..v.v
+"
+        );
+
+        assert_eq!(
+            get_state_after(&herd, 58).to_string(),
+            "..>>v>vv..
+..v.>>vv..
+..>>v>>vv.
+..>>>>>vv.
+v......>vv
+v>v....>>v
+vvv.....>>
+>vv......>
+.>v.vv.v..
+"
+        );
+    }
+
+    #[test]
+    fn test_find_stable_step() {
+        let herd = parsed_input(TEST_INPUT);
+
+        assert_eq!(find_stable_step(&herd), 58);
+    }
+
+    example_test!(test_solve, TEST_INPUT => 58);
 }
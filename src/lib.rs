@@ -1,11 +1,19 @@
+use std::env;
 use std::fmt::Debug;
 use std::fs::{read_to_string, File};
 use std::io::{BufRead, BufReader};
 use std::ops::{Add, Mul, Sub};
 use std::str::FromStr;
 
+pub fn get_input_path(filename: &str) -> String {
+    match env::var("AOC_INPUT") {
+        Ok(path) => path,
+        Err(_) => format!("input/{}", filename),
+    }
+}
+
 pub fn get_input(filename: &str) -> Vec<String> {
-    let file = match File::open(format!("input/{}", filename)) {
+    let file = match File::open(get_input_path(filename)) {
         Ok(file) => file,
         Err(error) => panic!("Unable to open file {}: {}", filename, error),
     };
@@ -16,7 +24,7 @@ pub fn get_input(filename: &str) -> Vec<String> {
 }
 
 pub fn get_input_as_string(filename: &str) -> String {
-    let reader = match read_to_string(format!("input/{}", filename)) {
+    let reader = match read_to_string(get_input_path(filename)) {
         Ok(r) => r,
         Err(error) => panic!("Unable to open file {}: {}", filename, error),
     };
@@ -35,3 +43,60 @@ where
         .map(|i| i.parse().unwrap())
         .collect()
 }
+
+pub fn lines(input: &str) -> Vec<String> {
+    input.lines().map(|l| l.to_string()).collect()
+}
+
+pub fn ints<T: FromStr>(input: &str) -> Vec<T>
+where
+    <T as FromStr>::Err: Debug,
+{
+    input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(|i| i.parse().unwrap())
+        .collect()
+}
+
+#[macro_export]
+macro_rules! example_test {
+    ($day:ident, $parse:path: $input:expr => $p1:expr, $p2:expr) => {
+        #[test]
+        fn $day() {
+            let input = $parse($input);
+            let (p1, p2) = solve(&*input);
+
+            assert_eq!(p1.to_string(), $p1.to_string());
+            assert_eq!(p2.to_string(), $p2.to_string());
+        }
+    };
+    ($day:ident, $parse:path: $input:expr => $p1:expr) => {
+        #[test]
+        fn $day() {
+            let input = $parse($input);
+            let (p1, _) = solve(&*input);
+
+            assert_eq!(p1.to_string(), $p1.to_string());
+        }
+    };
+    ($day:ident, $input:expr => $($answers:expr),+) => {
+        $crate::example_test!($day, $crate::lines: $input => $($answers),+);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{ints, lines};
+
+    #[test]
+    fn test_lines() {
+        assert_eq!(lines("a\n\nb c\n"), vec!["a", "", "b c"]);
+    }
+
+    #[test]
+    fn test_ints() {
+        assert_eq!(ints::<u32>("1\n2\n3"), vec![1, 2, 3]);
+        assert_eq!(ints::<i32>("3,-4,5\n"), vec![3, -4, 5]);
+    }
+}
@@ -0,0 +1,39 @@
+use std::fs;
+use std::path::Path;
+
+mod common;
+
+use common::{check_answers, run_solver};
+
+fn check_day(day: &str) {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("input");
+    let input = root.join(format!("{}.txt", day));
+    let expected = root.join(format!("{}.expected", day));
+
+    if !input.exists() || !expected.exists() {
+        eprintln!("Skipping {}: no input or recorded answers", day);
+        return;
+    }
+
+    let expected = fs::read_to_string(expected).unwrap();
+
+    if let Err(e) = run_solver(day, &input).and_then(|out| check_answers(&out, &expected)) {
+        panic!("{}: {}", day, e);
+    }
+}
+
+macro_rules! answer_tests {
+    ($($day:ident),+) => {
+        $(
+            #[test]
+            fn $day() {
+                check_day(stringify!($day));
+            }
+        )+
+    };
+}
+
+answer_tests!(
+    day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13,
+    day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25
+);
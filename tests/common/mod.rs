@@ -0,0 +1,58 @@
+use std::path::Path;
+use std::process::Command;
+
+macro_rules! bin_paths {
+    ($name:expr, $($day:ident),+) => {
+        match $name {
+            $(stringify!($day) => Ok(env!(concat!("CARGO_BIN_EXE_", stringify!($day)))),)+
+            _ => Err(format!("No binary for {}", $name)),
+        }
+    };
+}
+
+pub fn get_bin_path(name: &str) -> Result<&'static str, String> {
+    bin_paths!(
+        name, day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12,
+        day13, day14, day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25
+    )
+}
+
+pub fn run_solver(day: &str, input: &Path) -> Result<String, String> {
+    let output = Command::new(get_bin_path(day)?)
+        .env("AOC_INPUT", input)
+        .output()
+        .map_err(|e| format!("Unable to run {}: {}", day, e))?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "{} failed on {}: {}",
+            day,
+            input.display(),
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+pub fn check_answers(output: &str, expected: &str) -> Result<(), String> {
+    let output = output
+        .lines()
+        .filter(|l| !l.starts_with("Duration:"))
+        .map(|l| l.trim_end())
+        .collect::<Vec<&str>>();
+    let expected = expected
+        .lines()
+        .map(|l| l.trim_end())
+        .collect::<Vec<&str>>();
+
+    if output.starts_with(&expected) {
+        Ok(())
+    } else {
+        Err(format!(
+            "expected:\n{}\ngot:\n{}",
+            expected.join("\n"),
+            output.join("\n")
+        ))
+    }
+}
@@ -0,0 +1,71 @@
+use std::fs;
+use std::path::Path;
+
+mod common;
+
+use common::{check_answers, run_solver};
+
+static KNOWN_FAILURES: &[(&str, &str)] = &[];
+
+#[test]
+fn test_fixtures() {
+    let root = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+
+    let mut days = fs::read_dir(&root)
+        .unwrap()
+        .map(|e| e.unwrap().path())
+        .filter(|p| p.is_dir())
+        .collect::<Vec<_>>();
+    days.sort();
+
+    let mut nb_fixtures = 0;
+    let mut failures = Vec::new();
+
+    for dir in days {
+        let day = dir.file_name().unwrap().to_str().unwrap().to_string();
+
+        let mut inputs = fs::read_dir(&dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .filter(|p| p.extension().is_some_and(|e| e == "txt"))
+            .collect::<Vec<_>>();
+        inputs.sort();
+
+        for input in inputs {
+            nb_fixtures += 1;
+
+            let name = format!("{}/{}", day, input.file_name().unwrap().to_str().unwrap());
+            let expected = match fs::read_to_string(input.with_extension("expected")) {
+                Ok(expected) => expected,
+                Err(e) => {
+                    failures.push(format!("{}: missing expected answers: {}", name, e));
+                    continue;
+                }
+            };
+
+            let result = run_solver(&day, &input).and_then(|out| check_answers(&out, &expected));
+            let known_failure = KNOWN_FAILURES.iter().find(|(n, _)| *n == name);
+
+            match (result, known_failure) {
+                (Ok(()), None) => {}
+                (Err(e), None) => failures.push(format!("{}: {}", name, e)),
+                (Err(e), Some((_, reason))) => {
+                    eprintln!("{}: known failure ({}): {}", name, reason, e)
+                }
+                (Ok(()), Some(_)) => failures.push(format!(
+                    "{}: passes now, remove it from KNOWN_FAILURES",
+                    name
+                )),
+            }
+        }
+    }
+
+    assert!(nb_fixtures > 0, "No fixtures found in {}", root.display());
+    assert!(
+        failures.is_empty(),
+        "{} of {} fixtures failed:\n\n{}",
+        failures.len(),
+        nb_fixtures,
+        failures.join("\n\n")
+    );
+}